@@ -0,0 +1,112 @@
+use std::{collections::HashMap, fs, net::{IpAddr, SocketAddr}, path::PathBuf};
+use anyhow::Context;
+
+/// User configuration, read from `config.toml` in the config directory. Everything is
+/// optional; a missing file is the same as an empty one.
+#[derive(Debug, Default, Clone, serde::Deserialize)]
+#[serde(default)]
+pub struct Config {
+  /// Android TV devices to reach over ADB, which discovery cannot find on its own
+  pub android: Vec<ManualDevice>,
+
+  /// Kodi instances to reach over JSON-RPC, in addition to any found over SSDP
+  pub kodi: Vec<ManualDevice>,
+
+  /// Login for Roku developer mode's web installer
+  pub developer: DeveloperCredentials,
+
+  /// Short names for devices on the command line, each standing for a name, serial number or IP address
+  pub aliases: HashMap<String, String>
+}
+
+/// The login set when developer mode was enabled on the device
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(default)]
+pub struct DeveloperCredentials {
+  /// Always `rokudev` on current firmware
+  pub username: String,
+  pub password: Option<String>,
+
+  /// Password of the signing key `genkey` put on the device, for packaging
+  pub signing_password: Option<String>
+}
+
+impl Default for DeveloperCredentials {
+  fn default() -> Self {
+    DeveloperCredentials { username: "rokudev".to_owned(), password: None, signing_password: None }
+  }
+}
+
+/// A device added by hand because it cannot be discovered
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct ManualDevice {
+  /// IP address or host name, optionally with a port (IPv6 addresses in brackets when they have one)
+  pub address: String,
+
+  /// Credentials, for platforms whose API can require them
+  pub username: Option<String>,
+  pub password: Option<String>
+}
+
+impl ManualDevice {
+  /// `address` with `default_port` filled in if it names none
+  pub fn address_with_port(&self, default_port: u16) -> String {
+    if let Ok(ip) = self.address.parse::<IpAddr>() { return SocketAddr::new(ip, default_port).to_string() }
+    if self.address.parse::<SocketAddr>().is_ok() { return self.address.clone() }
+
+    // host names, and bracketed addresses the parsers above do not take, such as scoped IPv6 ones
+    match self.address.rsplit_once(':') {
+      Some((host, port)) if port.parse::<u16>().is_ok() && (!host.contains(':') || host.ends_with(']')) => self.address.clone(),
+      _ => format!("{}:{}", self.address, default_port)
+    }
+  }
+}
+
+impl Config {
+  /// Reads the configuration file. Fails only if the file exists but cannot be used.
+  pub fn load() -> anyhow::Result<Self> {
+    let path = Self::path();
+
+    match fs::read_to_string(&path) {
+      Ok(contents) => toml::from_str(&contents).with_context(|| format!("Invalid configuration in {}", path.display())),
+      Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Config::default()),
+      Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display()))
+    }
+  }
+
+  pub fn path() -> PathBuf { config_dir().join("config.toml") }
+}
+
+/// `$XDG_CONFIG_HOME/mote`, falling back to `~/.config/mote`
+pub fn config_dir() -> PathBuf {
+  std::env::var_os("XDG_CONFIG_HOME")
+    .map(PathBuf::from)
+    .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+    .unwrap_or_default()
+    .join("mote")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn manual(address: &str) -> ManualDevice {
+    ManualDevice { address: address.to_owned(), username: None, password: None }
+  }
+
+  #[test]
+  fn fills_in_missing_ports() {
+    assert_eq!(manual("192.168.1.50").address_with_port(5555), "192.168.1.50:5555");
+    assert_eq!(manual("192.168.1.50:5556").address_with_port(5555), "192.168.1.50:5556");
+    assert_eq!(manual("shield.local").address_with_port(5555), "shield.local:5555");
+    assert_eq!(manual("shield.local:5556").address_with_port(5555), "shield.local:5556");
+  }
+
+  #[test]
+  fn brackets_ipv6_addresses() {
+    assert_eq!(manual("fe80::1").address_with_port(5555), "[fe80::1]:5555");
+    assert_eq!(manual("::1").address_with_port(5555), "[::1]:5555");
+    assert_eq!(manual("[fe80::1]").address_with_port(5555), "[fe80::1]:5555");
+    assert_eq!(manual("[fe80::1]:5556").address_with_port(5555), "[fe80::1]:5556");
+  }
+}
@@ -0,0 +1,113 @@
+use std::{collections::HashMap, ffi::OsString};
+use anyhow::bail;
+use tokio::process::Command;
+
+/// Drives a device through the local `adb` client, connected over TCP.
+#[derive(Debug, Clone)]
+pub struct Adb {
+  /// The adb executable, `adb` on the PATH unless overridden
+  program: OsString,
+
+  /// `host:port` the device is connected as, which is also its adb serial
+  serial: String
+}
+
+impl Adb {
+  pub fn new(program: impl Into<OsString>, serial: &str) -> Self {
+    Adb { program: program.into(), serial: serial.to_owned() }
+  }
+
+  /// `host:port` the device is connected as
+  pub fn serial(&self) -> &str { &self.serial }
+
+  /// Connects the adb server to the device. The first connection prompts on the TV to allow
+  /// debugging from this computer; adb remembers the answer.
+  pub async fn connect(&self) -> anyhow::Result<()> {
+    let output = self.run(&["connect", &self.serial]).await?;
+
+    // adb reports connection failures on stdout with a zero exit code
+    if !output.contains("connected to") { bail!("adb could not connect to {}: {}", self.serial, output.trim()) }
+    Ok(())
+  }
+
+  /// Runs a shell command on the device, returning its output.
+  pub async fn shell(&self, command: &str) -> anyhow::Result<String> {
+    self.run(&["-s", &self.serial, "shell", command]).await
+  }
+
+  /// All system properties, from `getprop`
+  pub async fn properties(&self) -> anyhow::Result<HashMap<String, String>> {
+    Ok(parse_properties(&self.shell("getprop").await?))
+  }
+
+  async fn run(&self, args: &[&str]) -> anyhow::Result<String> {
+    let output = Command::new(&self.program).args(args).output().await?;
+
+    if !output.status.success() {
+      bail!("adb {} failed: {}", args.join(" "), String::from_utf8_lossy(&output.stderr).trim())
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+  }
+}
+
+/// Parses `getprop` output, lines of the form `[ro.product.model]: [SHIELD Android TV]`.
+pub fn parse_properties(output: &str) -> HashMap<String, String> {
+  output
+    .lines()
+    .filter_map(|line| {
+      let (key, value) = line.split_once("]: [")?;
+      Some((key.strip_prefix('[')?.to_owned(), value.strip_suffix(']')?.to_owned()))
+    })
+    .collect()
+}
+
+/// Quotes text for `input text`, which runs through the device's shell and reads `%s` as a space.
+pub fn quote_text(text: &str) -> String {
+  format!("'{}'", text.replace('\'', r"'\''").replace(' ', "%s"))
+}
+
+/// Whether `name` could be a package name, and so is safe to put in a shell command unquoted
+pub fn is_package_name(name: &str) -> bool {
+  !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parses_getprop_output() {
+    let properties = parse_properties("[ro.product.model]: [SHIELD Android TV]\n[ro.serialno]: [1234]\ngarbage\n[empty]: []\n");
+
+    assert_eq!(properties["ro.product.model"], "SHIELD Android TV");
+    assert_eq!(properties["ro.serialno"], "1234");
+    assert_eq!(properties["empty"], "");
+    assert_eq!(properties.len(), 3);
+  }
+
+  #[test]
+  fn quotes_text_for_input() {
+    assert_eq!(quote_text("star wars"), "'star%swars'");
+    assert_eq!(quote_text("it's"), r"'it'\''s'");
+  }
+
+  #[test]
+  fn checks_package_names() {
+    assert!(is_package_name("com.netflix.ninja"));
+    assert!(is_package_name("org.xbmc.kodi_21"));
+    assert!(!is_package_name(""));
+    assert!(!is_package_name("com.example; reboot"));
+    assert!(!is_package_name("$(reboot)"));
+  }
+
+  #[tokio::test]
+  async fn reports_failed_connections() {
+    // `echo` stands in for adb, printing what a refused connection looks like
+    let adb = Adb::new("echo", "failed to connect to 10.0.0.1:5555");
+    assert!(adb.connect().await.is_err());
+
+    let adb = Adb::new("echo", "connected to 10.0.0.1:5555");
+    assert!(adb.connect().await.is_ok());
+  }
+}
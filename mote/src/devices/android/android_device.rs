@@ -0,0 +1,149 @@
+use std::{net::SocketAddr, sync::Arc};
+use anyhow::{anyhow, bail};
+use futures::{future::BoxFuture, TryFutureExt};
+use tokio::sync::mpsc::UnboundedReceiver;
+use crate::{config::Config, devices::{
+  App, Capabilities, Capability, Device, DeviceInfo, DeviceInput, DeviceType, FormFactor, Model, Network, NetworkType,
+  Product, RemoteDevice, System, Uptime,
+  manual
+}};
+use super::{android_key_code, is_package_name, quote_text, Adb};
+
+/// Port adb listens on once network debugging is enabled
+const ADB_PORT: u16 = 5555;
+
+/// An Android TV or Google TV device, controlled through adb over TCP.
+#[derive(Debug, Clone)]
+pub struct AndroidDevice {
+  location: SocketAddr,
+  info: DeviceInfo,
+  adb: Adb
+}
+
+impl AndroidDevice {
+  /// Connects adb to `address` (`host:port`) and reads device info.
+  pub async fn query(address: String) -> anyhow::Result<Self> {
+    let location = tokio::net::lookup_host(address.as_str())
+      .await?
+      .next()
+      .ok_or_else(|| anyhow!("{} does not resolve to an address", address))?;
+
+    let adb = Adb::new("adb", &address);
+    adb.connect().await?;
+
+    let info = Self::device_info(&adb, &address).await?;
+
+    Ok(AndroidDevice { location, info, adb })
+  }
+
+  async fn device_info(adb: &Adb, address: &str) -> anyhow::Result<DeviceInfo> {
+    let mut properties = adb.properties().await?;
+    let mut property = |key: &str| properties.remove(key).filter(|v| !v.is_empty());
+
+    let model_name = property("ro.product.model").unwrap_or_default();
+    let name = adb.shell("settings get global device_name").await?.trim().to_owned();
+
+    let uptime = adb
+      .shell("cat /proc/uptime")
+      .await?
+      .split_whitespace()
+      .next()
+      .and_then(|seconds| seconds.parse::<f64>().ok())
+      .map(|seconds| Uptime::new(seconds as u64));
+
+    Ok(DeviceInfo {
+      name: if name.is_empty() || name == "null" { model_name.clone() } else { name },
+      product: Product {
+        vendor: property("ro.product.manufacturer").unwrap_or_default(),
+        // some builds hide the serial number, the address is the next best identifier
+        serial_number: property("ro.serialno").unwrap_or_else(|| address.to_owned()),
+        model: Model {
+          alternate_name: property("ro.product.name").unwrap_or_default(),
+          number: property("ro.product.device").unwrap_or_default(),
+          name: model_name
+        },
+        // Android TV runs on TVs and streamers alike, with nothing reliable to tell them apart
+        form_factor: FormFactor::Unknown
+      },
+      network: Network {
+        network_type: NetworkType::Unknown,
+        network_name: String::new(),
+        mac_address: String::new()
+      },
+      system: System {
+        uptime,
+        software_version: property("ro.build.version.release").map(|release| format!("Android {}", release)),
+        time_zone: property("persist.sys.timezone"),
+        ..System::default()
+      },
+      now_playing: None,
+      features: vec![]
+    })
+  }
+}
+
+impl RemoteDevice for AndroidDevice {
+  /// Android devices do not answer SSDP searches, so they are connected to by the addresses
+  /// listed under `[[android]]` in the configuration file.
+  fn discover() -> UnboundedReceiver<Device> {
+    let addresses = Config::load()
+      .unwrap_or_default()
+      .android
+      .iter()
+      .map(|d| d.address_with_port(ADB_PORT))
+      .collect();
+
+    manual::connect_all(addresses, |address| async move {
+      Ok(Arc::new(AndroidDevice::query(address).await?) as Device)
+    })
+  }
+
+  fn device_type(&self) -> DeviceType { DeviceType::Android }
+
+  fn location(&self) -> Option<SocketAddr> { Some(self.location) }
+
+  fn info(&self) -> &DeviceInfo { &self.info }
+
+  fn capabilities(&self) -> Capabilities {
+    Capabilities::from([
+      Capability::Power, Capability::Volume, Capability::InstantReplay, Capability::Info, Capability::Text, Capability::Apps
+    ])
+  }
+
+  fn send_input(&self, input: DeviceInput) -> BoxFuture<'_, crate::Result<()>> {
+    Box::pin(async move {
+      let command = match input {
+        DeviceInput::KeyPress(key) => format!("input keyevent {}", android_key_code(key).ok_or_else(|| self.unsupported_key(key))?),
+        DeviceInput::Text(text) => format!("input text {}", quote_text(&text)),
+        DeviceInput::Tune(_) | DeviceInput::SwitchInput(_) => bail!("Android devices cannot tune channels or switch inputs from mote"),
+        DeviceInput::Launch(package) if is_package_name(&package) => format!("monkey -p {} -c android.intent.category.LEANBACK_LAUNCHER 1", package),
+        DeviceInput::Launch(package) => bail!("{:?} is not an Android package name", package)
+      };
+
+      self.adb.shell(&command).await?;
+      Ok(())
+    }.err_into())
+  }
+
+  fn query_state(&self) -> BoxFuture<'_, crate::Result<Device>> {
+    Box::pin(async move {
+      let info = Self::device_info(&self.adb, self.adb.serial()).await?;
+      Ok(Arc::new(AndroidDevice { info, ..self.clone() }) as Device)
+    })
+  }
+
+  /// Apps from the package manager; Android has no separate display name to offer, so the package name stands in
+  fn apps(&self) -> BoxFuture<'_, anyhow::Result<Vec<App>>> {
+    Box::pin(async move {
+      let packages = self.adb.shell("pm list packages -3").await?;
+
+      Ok(
+        packages
+          .lines()
+          .filter_map(|line| line.trim().strip_prefix("package:"))
+          .map(|package| App { id: package.to_owned(), name: package.to_owned() })
+          .collect()
+      )
+    })
+  }
+}
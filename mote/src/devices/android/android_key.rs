@@ -0,0 +1,21 @@
+use crate::devices::Key;
+
+/// Maps mote's keys onto Android `KeyEvent` codes, as accepted by `input keyevent`.
+pub fn android_key_code(key: Key) -> Option<&'static str> {
+  Some(match key {
+    Key::Power         => "KEYCODE_POWER",
+    Key::Home          => "KEYCODE_HOME",
+    Key::Back          => "KEYCODE_BACK",
+    Key::Ok            => "KEYCODE_DPAD_CENTER",
+    Key::Up            => "KEYCODE_DPAD_UP",
+    Key::Down          => "KEYCODE_DPAD_DOWN",
+    Key::Left          => "KEYCODE_DPAD_LEFT",
+    Key::Right         => "KEYCODE_DPAD_RIGHT",
+    Key::InstantReplay => "KEYCODE_MEDIA_REWIND",
+    Key::Info          => "KEYCODE_INFO",
+    Key::VolumeUp      => "KEYCODE_VOLUME_UP",
+    Key::VolumeDown    => "KEYCODE_VOLUME_DOWN",
+    Key::VolumeMute    => "KEYCODE_VOLUME_MUTE",
+    Key::FindRemote    => return None
+  })
+}
@@ -0,0 +1,7 @@
+mod adb;
+mod android_device;
+mod android_key;
+
+pub use adb::*;
+pub use android_device::*;
+pub use android_key::*;
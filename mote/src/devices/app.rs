@@ -0,0 +1,9 @@
+/// An installed app (channel, on Roku) that can be launched.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct App {
+  /// Platform-specific id used to launch it
+  pub id: String,
+
+  /// Human-readable name
+  pub name: String
+}
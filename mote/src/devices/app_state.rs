@@ -0,0 +1,38 @@
+use std::fmt::{Display, Formatter, Result};
+
+/// What a device is doing right now: the app in the foreground and, if it has one open,
+/// what its media player is up to.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct AppState {
+  /// The foreground app's platform-specific id, `None` on the home screen
+  pub app_id: Option<String>,
+
+  pub app_name: String,
+
+  pub media: Option<MediaState>
+}
+
+/// A media player's state, as of one query.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct MediaState {
+  /// As the platform names it, e.g. `play`, `pause` or `buffer` on Roku
+  pub state: String,
+
+  /// How far into the stream playback is, in milliseconds
+  pub position_ms: Option<u64>,
+
+  /// Length of the stream in milliseconds, unknown for live streams
+  pub duration_ms: Option<u64>
+}
+
+impl Display for AppState {
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+    write!(f, "{}", self.app_name)?;
+
+    match &self.media {
+      Some(MediaState { state, position_ms: Some(position), .. }) => write!(f, " ({} at {:.1}s)", state, *position as f64 / 1000.0),
+      Some(MediaState { state, .. }) => write!(f, " ({})", state),
+      None => Ok(())
+    }
+  }
+}
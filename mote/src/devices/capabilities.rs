@@ -0,0 +1,98 @@
+use std::collections::HashSet;
+use super::Key;
+
+/// Something a device may or may not be able to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize)]
+pub enum Capability {
+  Power,
+  Volume,
+  InstantReplay,
+  Info,
+
+  /// Ring the remote so it can be found
+  FindRemote,
+
+  /// Type text into the focused field
+  Text,
+
+  /// List and launch installed apps
+  Apps,
+
+  /// Switch between the TV's own inputs (HDMI, AV, tuner)
+  TvInputs,
+
+  /// Tune broadcast channels
+  Tuner,
+
+  /// Capture the screen, as Roku developer mode allows
+  Screenshot,
+
+  /// Stream the output of sideloaded apps, as Roku developer mode allows
+  DebugConsole
+}
+
+impl Capability {
+  /// What a key needs from the device; navigation keys need nothing.
+  pub fn for_key(key: Key) -> Option<Capability> {
+    match key {
+      Key::Power => Some(Capability::Power),
+      Key::VolumeUp | Key::VolumeDown | Key::VolumeMute => Some(Capability::Volume),
+      Key::InstantReplay => Some(Capability::InstantReplay),
+      Key::Info => Some(Capability::Info),
+      Key::FindRemote => Some(Capability::FindRemote),
+      Key::Home | Key::Back | Key::Ok | Key::Up | Key::Down | Key::Left | Key::Right => None
+    }
+  }
+}
+
+/// Everything a device can do, so the UI only offers what will work.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Capabilities(HashSet<Capability>);
+
+impl Capabilities {
+  pub fn has(&self, capability: Capability) -> bool { self.0.contains(&capability) }
+
+  pub fn supports_key(&self, key: Key) -> bool {
+    Capability::for_key(key).is_none_or(|capability| self.has(capability))
+  }
+}
+
+/// Serialized as a list, in a stable order
+impl serde::Serialize for Capabilities {
+  fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    let mut capabilities: Vec<_> = self.0.iter().collect();
+    capabilities.sort();
+    serializer.collect_seq(capabilities)
+  }
+}
+
+impl<const N: usize> From<[Capability; N]> for Capabilities {
+  fn from(capabilities: [Capability; N]) -> Self { Capabilities(capabilities.into()) }
+}
+
+impl FromIterator<Capability> for Capabilities {
+  fn from_iter<I: IntoIterator<Item = Capability>>(capabilities: I) -> Self { Capabilities(capabilities.into_iter().collect()) }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn navigation_needs_nothing() {
+    let none = Capabilities::default();
+
+    assert!(none.supports_key(Key::Up));
+    assert!(none.supports_key(Key::Ok));
+    assert!(!none.supports_key(Key::VolumeUp));
+    assert!(!none.supports_key(Key::Power));
+  }
+
+  #[test]
+  fn keys_follow_capabilities() {
+    let capabilities = Capabilities::from([Capability::Volume]);
+
+    assert!(capabilities.supports_key(Key::VolumeMute));
+    assert!(!capabilities.supports_key(Key::FindRemote));
+  }
+}
@@ -0,0 +1,256 @@
+use std::{collections::HashMap, ffi::OsString, process::Stdio};
+use anyhow::{anyhow, bail};
+use tokio::{io::AsyncWriteExt, process::Command, sync::Mutex};
+
+/// Name mote announces itself with on the bus, which is also how it recognizes itself in a scan
+const OSD_NAME: &str = "mote";
+
+/// Logical address mote transmits from; `cec-client -t r` registers as Recording Device 1
+const OWN_ADDRESS: u8 = 1;
+
+/// `<User Control Pressed>` and `<User Control Released>` opcodes
+const USER_CONTROL_PRESSED: u8 = 0x44;
+const USER_CONTROL_RELEASED: u8 = 0x45;
+
+/// Drives the CEC bus through the local `cec-client` from libcec. Every command (or pair of
+/// commands, for a keypress) runs in a fresh process, and only one may hold the adapter at a
+/// time, so commands are serialized through the client.
+#[derive(Debug)]
+pub struct CecClient {
+  /// The cec-client executable, `cec-client` on the PATH unless overridden
+  program: OsString,
+
+  /// Held while a cec-client process owns the adapter
+  adapter: Mutex<()>
+}
+
+/// One device as reported by the `scan` command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BusDevice {
+  /// Logical address, 0 (TV) through 14
+  pub logical_address: u8,
+
+  /// Device type as cec-client names it, e.g. `TV` or `Playback 1`
+  pub type_name: String,
+
+  /// Physical address in dotted form, e.g. `1.0.0.0`
+  pub physical_address: String,
+
+  pub vendor: String,
+  pub osd_name: String,
+  pub cec_version: String,
+  pub power_status: String
+}
+
+impl CecClient {
+  pub fn new(program: impl Into<OsString>) -> Self {
+    CecClient { program: program.into(), adapter: Mutex::new(()) }
+  }
+
+  /// Every device on the bus other than mote itself.
+  pub async fn scan(&self) -> anyhow::Result<Vec<BusDevice>> {
+    Ok(
+      parse_scan(&self.run(&["scan".into()]).await?)
+        .into_iter()
+        .filter(|d| d.osd_name != OSD_NAME)
+        .collect()
+    )
+  }
+
+  /// Power status of a device: `on`, `standby` or one of the transition states.
+  pub async fn power_status(&self, logical_address: u8) -> anyhow::Result<String> {
+    let output = self.run(&[format!("pow {:x}", logical_address)]).await?;
+
+    let status = output
+      .lines()
+      .find_map(|line| line.trim().strip_prefix("power status:"))
+      .map(|status| status.trim().to_owned())
+      .ok_or_else(|| anyhow!("cec-client did not report a power status"))?;
+
+    // cec-client reports `unknown` when the device did not answer at all
+    if status == "unknown" { bail!("CEC device {:x} did not answer", logical_address) }
+    Ok(status)
+  }
+
+  /// Turns a device on or puts it in standby.
+  pub async fn set_power(&self, logical_address: u8, on: bool) -> anyhow::Result<()> {
+    self.run(&[format!("{} {:x}", if on { "on" } else { "standby" }, logical_address)]).await?;
+    Ok(())
+  }
+
+  /// Presses and releases a remote control button on a device, opening the adapter once for both.
+  pub async fn press(&self, logical_address: u8, code: u8) -> anyhow::Result<()> {
+    let header = format!("{:x}{:x}", OWN_ADDRESS, logical_address);
+
+    self.run(&[
+      format!("tx {}:{:02x}:{:02x}", header, USER_CONTROL_PRESSED, code),
+      format!("tx {}:{:02x}", header, USER_CONTROL_RELEASED)
+    ]).await?;
+    Ok(())
+  }
+
+  /// Runs cec-client commands in one process, returning everything it printed.
+  async fn run(&self, commands: &[String]) -> anyhow::Result<String> {
+    let _adapter = self.adapter.lock().await;
+
+    // single-command mode exits after the first line, otherwise cec-client runs until told to quit
+    let (mode, script) = match commands {
+      [command] => (&["-s"][..], format!("{}\n", command)),
+      _ => (&[][..], format!("{}\nq\n", commands.join("\n")))
+    };
+
+    let mut child = Command::new(&self.program)
+      .args(mode)
+      .args(["-d", "1", "-t", "r", "-o", OSD_NAME])
+      .stdin(Stdio::piped())
+      .stdout(Stdio::piped())
+      .stderr(Stdio::piped())
+      .spawn()?;
+
+    let mut stdin = child.stdin.take().ok_or_else(|| anyhow!("cec-client stdin unavailable"))?;
+    stdin.write_all(script.as_bytes()).await?;
+    drop(stdin);
+
+    let output = child.wait_with_output().await?;
+
+    if !output.status.success() {
+      bail!("cec-client {} failed: {}", commands.join(", "), String::from_utf8_lossy(&output.stderr).trim())
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+  }
+}
+
+/// Parses the output of `scan`: a block per device, headed `device #<n>: <type>` and followed
+/// by `key: value` lines.
+pub fn parse_scan(output: &str) -> Vec<BusDevice> {
+  let mut devices = vec![];
+  let mut current: Option<(u8, String, HashMap<String, String>)> = None;
+
+  for line in output.lines().map(str::trim) {
+    if let Some(header) = line.strip_prefix("device #") {
+      devices.extend(current.take().map(bus_device));
+
+      current = header
+        .split_once(':')
+        .and_then(|(address, type_name)| Some((address.trim().parse().ok()?, type_name.trim().to_owned(), HashMap::new())));
+    } else if let (Some((_, _, fields)), Some((key, value))) = (&mut current, line.split_once(':')) {
+      fields.insert(key.trim().to_owned(), value.trim().to_owned());
+    }
+  }
+
+  devices.extend(current.map(bus_device));
+  devices
+}
+
+fn bus_device((logical_address, type_name, mut fields): (u8, String, HashMap<String, String>)) -> BusDevice {
+  let mut field = |key: &str| fields.remove(key).unwrap_or_default();
+
+  BusDevice {
+    logical_address,
+    type_name,
+    physical_address: field("address"),
+    vendor: field("vendor"),
+    osd_name: field("osd string"),
+    cec_version: field("CEC version"),
+    power_status: field("power status")
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{fs, os::unix::fs::PermissionsExt, path::PathBuf};
+  use super::*;
+
+  const SCAN: &str = "\
+opening a connection to the CEC adapter...
+requesting CEC bus information ...
+CEC bus information
+===================
+device #0: TV
+address:       0.0.0.0
+active source: no
+vendor:        LG
+osd string:    TV
+CEC version:   1.4
+power status:  standby
+language:      eng
+
+
+device #1: Recorder 1
+address:       1.0.0.0
+active source: no
+vendor:        Pulse Eight
+osd string:    mote
+CEC version:   1.4
+power status:  on
+language:      eng
+
+
+device #4: Playback 1
+address:       2.0.0.0
+active source: yes
+vendor:        Unknown
+osd string:    Chromecast
+CEC version:   1.4
+power status:  on
+language:      ???
+
+
+currently active source: Playback 1 (4)
+";
+
+  /// Writes a stand-in cec-client that logs each process started and each command it is given,
+  /// and answers `scan` and `pow` the way the real one does. Returns the script and its log.
+  fn fake_cec_client(name: &str) -> (PathBuf, PathBuf) {
+    let dir = std::env::temp_dir().join(format!("mote-cec-{}-{}", std::process::id(), name));
+    fs::create_dir_all(&dir).unwrap();
+
+    let script = dir.join("cec-client");
+    let log = dir.join("commands.log");
+    let _ = fs::remove_file(&log);
+
+    fs::write(&script, format!(
+      "#!/bin/sh\necho start >> '{0}'\nwhile read command && [ \"$command\" != q ]; do\necho \"$command\" >> '{0}'\ncase \"$command\" in\n  scan) cat <<'EOF'\n{1}EOF\n  ;;\n  pow*) echo 'power status: standby' ;;\nesac\ndone\n",
+      log.display(), SCAN
+    )).unwrap();
+    fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+
+    (script, log)
+  }
+
+  #[test]
+  fn parses_scan_output() {
+    let devices = parse_scan(SCAN);
+
+    assert_eq!(devices.len(), 3);
+    assert_eq!(devices[0].logical_address, 0);
+    assert_eq!(devices[0].type_name, "TV");
+    assert_eq!(devices[0].vendor, "LG");
+    assert_eq!(devices[0].power_status, "standby");
+    assert_eq!(devices[2].logical_address, 4);
+    assert_eq!(devices[2].physical_address, "2.0.0.0");
+    assert_eq!(devices[2].osd_name, "Chromecast");
+  }
+
+  #[tokio::test]
+  async fn scan_leaves_out_mote_itself() {
+    let (script, _) = fake_cec_client("scan");
+    let devices = CecClient::new(script).scan().await.unwrap();
+
+    assert_eq!(devices.iter().map(|d| d.logical_address).collect::<Vec<_>>(), vec![0, 4]);
+  }
+
+  #[tokio::test]
+  async fn presses_and_releases_buttons() {
+    let (script, log) = fake_cec_client("press");
+    let client = CecClient::new(script);
+
+    client.press(4, 0x41).await.unwrap();
+    assert_eq!(client.power_status(0).await.unwrap(), "standby");
+    client.set_power(0, true).await.unwrap();
+
+    // the press and release share one process
+    assert_eq!(fs::read_to_string(log).unwrap(), "start\ntx 14:44:41\ntx 14:45\nstart\npow 0\nstart\non 0\n");
+  }
+}
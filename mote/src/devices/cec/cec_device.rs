@@ -0,0 +1,125 @@
+use std::{net::SocketAddr, sync::Arc};
+use anyhow::bail;
+use futures::{future::BoxFuture, TryFutureExt};
+use tokio::{spawn, sync::mpsc::{unbounded_channel, UnboundedReceiver}};
+use crate::devices::{
+  Capabilities, Capability, Device, DeviceInfo, DeviceInput, DeviceType, FormFactor, Model, Network, NetworkType,
+  PowerMode, Product, RemoteDevice, System
+};
+use super::{BusDevice, CecClient, CecKey};
+
+/// A display or player on the HDMI-CEC bus, reached through a CEC adapter on this machine.
+#[derive(Debug, Clone)]
+pub struct CecDevice {
+  logical_address: u8,
+  physical_address: String,
+  info: DeviceInfo,
+  client: Arc<CecClient>
+}
+
+impl CecDevice {
+  pub fn new(bus_device: BusDevice, client: Arc<CecClient>) -> Self {
+    let name = match bus_device.osd_name.as_str() {
+      "" => bus_device.type_name.clone(),
+      osd_name => osd_name.to_owned()
+    };
+
+    let info = DeviceInfo {
+      name,
+      product: Product {
+        vendor: bus_device.vendor,
+        // CEC devices carry no serial number, but a physical address is unique on its bus
+        serial_number: format!("cec-{}", bus_device.physical_address),
+        model: Model {
+          name: bus_device.type_name,
+          alternate_name: String::new(),
+          number: format!("CEC {}", bus_device.cec_version)
+        },
+        form_factor: match bus_device.logical_address {
+          0 => FormFactor::Tv,
+          // playback devices: 4, 8 and 11
+          4 | 8 | 11 => FormFactor::Streamer,
+          _ => FormFactor::Unknown
+        }
+      },
+      network: Network {
+        network_type: NetworkType::Unknown,
+        network_name: String::new(),
+        mac_address: String::new()
+      },
+      system: System {
+        power_mode: match bus_device.power_status.as_str() {
+          "on" => Some(PowerMode::On),
+          "standby" => Some(PowerMode::Standby),
+          _ => None
+        },
+        ..System::default()
+      },
+      now_playing: None,
+      features: vec![]
+    };
+
+    CecDevice {
+      logical_address: bus_device.logical_address,
+      physical_address: bus_device.physical_address,
+      info,
+      client
+    }
+  }
+}
+
+impl RemoteDevice for CecDevice {
+  /// Scans the bus once through the local adapter; every device on it shares the one client.
+  fn discover() -> UnboundedReceiver<Device> {
+    let (tx, rx) = unbounded_channel::<Device>();
+    let client = Arc::new(CecClient::new("cec-client"));
+
+    spawn(async move {
+      // no adapter (or no cec-client) simply means no CEC devices
+      let Ok(bus_devices) = client.scan().await else { return };
+
+      for bus_device in bus_devices {
+        if tx.send(Arc::new(CecDevice::new(bus_device, client.clone()))).is_err() { break }
+      }
+    });
+
+    rx
+  }
+
+  fn device_type(&self) -> DeviceType { DeviceType::Cec }
+
+  fn location(&self) -> Option<SocketAddr> { None }
+
+  fn info(&self) -> &DeviceInfo { &self.info }
+
+  fn capabilities(&self) -> Capabilities {
+    Capabilities::from([Capability::Power, Capability::Volume, Capability::InstantReplay, Capability::Info])
+  }
+
+  fn send_input(&self, input: DeviceInput) -> BoxFuture<'_, crate::Result<()>> {
+    Box::pin(async move {
+      match input {
+        DeviceInput::KeyPress(key) => match CecKey::for_key(key).ok_or_else(|| self.unsupported_key(key))? {
+          CecKey::UserControl(code) => self.client.press(self.logical_address, code).await,
+          CecKey::TogglePower => {
+            let on = self.client.power_status(self.logical_address).await? == "on";
+            self.client.set_power(self.logical_address, !on).await
+          }
+        },
+        DeviceInput::Text(_) => bail!("CEC devices do not accept typed text"),
+        DeviceInput::Tune(_) | DeviceInput::SwitchInput(_) => bail!("CEC devices cannot tune channels or switch inputs"),
+        DeviceInput::Launch(_) => bail!("CEC devices cannot launch apps")
+      }
+    }.err_into())
+  }
+
+  /// CEC has nothing to refresh beyond whether the device still answers
+  fn query_state(&self) -> BoxFuture<'_, crate::Result<Device>> {
+    Box::pin(async move {
+      self.client.power_status(self.logical_address).await?;
+      Ok(Arc::new(self.clone()) as Device)
+    })
+  }
+
+  fn address(&self) -> String { format!("CEC {}", self.physical_address) }
+}
@@ -0,0 +1,33 @@
+use crate::devices::Key;
+
+/// How one of mote's keys is delivered over CEC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CecKey {
+  /// A `<User Control Pressed>` operand, as defined in the HDMI-CEC spec
+  UserControl(u8),
+
+  /// Power has no reliable toggle operand, so it is sent as `on` or `standby` depending on state
+  TogglePower
+}
+
+impl CecKey {
+  /// How `key` is delivered, if CEC has an equivalent
+  pub fn for_key(key: Key) -> Option<CecKey> {
+    Some(CecKey::UserControl(match key {
+      Key::Power         => return Some(CecKey::TogglePower),
+      Key::Home          => 0x09, // Root Menu
+      Key::Back          => 0x0D, // Exit
+      Key::Ok            => 0x00, // Select
+      Key::Up            => 0x01,
+      Key::Down          => 0x02,
+      Key::Left          => 0x03,
+      Key::Right         => 0x04,
+      Key::InstantReplay => 0x48, // Rewind
+      Key::Info          => 0x35, // Display Information
+      Key::VolumeUp      => 0x41,
+      Key::VolumeDown    => 0x42,
+      Key::VolumeMute    => 0x43,
+      Key::FindRemote    => return None
+    }))
+  }
+}
@@ -0,0 +1,7 @@
+mod cec_client;
+mod cec_device;
+mod cec_key;
+
+pub use cec_client::*;
+pub use cec_device::*;
+pub use cec_key::*;
@@ -0,0 +1,34 @@
+use std::fmt::{Display, Formatter, Result};
+
+/// A broadcast channel the TV's tuner has found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Channel {
+  /// Channel number as broadcast, e.g. `2.1`; also what it is tuned by
+  pub number: String,
+
+  /// Station name, e.g. `KTVU-HD`
+  pub name: String
+}
+
+/// A TV's own video sources.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum TvInput {
+  Tuner,
+  Hdmi(u8),
+  Av
+}
+
+impl TvInput {
+  /// The inputs offered for switching, in the order TVs usually list them
+  pub const ALL: [TvInput; 6] = [TvInput::Tuner, TvInput::Hdmi(1), TvInput::Hdmi(2), TvInput::Hdmi(3), TvInput::Hdmi(4), TvInput::Av];
+}
+
+impl Display for TvInput {
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+    match self {
+      TvInput::Tuner => write!(f, "Antenna TV"),
+      TvInput::Hdmi(port) => write!(f, "HDMI {}", port),
+      TvInput::Av => write!(f, "AV")
+    }
+  }
+}
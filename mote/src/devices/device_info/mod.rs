@@ -0,0 +1,31 @@
+mod product;
+mod model;
+mod network;
+mod uptime;
+mod system;
+
+pub use product::*;
+pub use model::*;
+pub use network::*;
+pub use uptime::*;
+pub use system::*;
+
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct DeviceInfo {
+  pub name: String,
+  pub product: Product,
+  pub network: Network,
+  pub system: System,
+
+  /// What is playing right now, for devices that report it
+  pub now_playing: Option<String>,
+
+  /// Optional features the device reports having (or lacking), in display order
+  #[serde(serialize_with = "serialize_features")]
+  pub features: Vec<(&'static str, bool)>
+}
+
+/// Features as an object of name to whether the device has it, rather than pairs
+fn serialize_features<S: serde::Serializer>(features: &[(&'static str, bool)], serializer: S) -> Result<S::Ok, S::Error> {
+  serializer.collect_map(features.iter().copied())
+}
\ No newline at end of file
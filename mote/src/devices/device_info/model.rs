@@ -1,5 +1,5 @@
 /// Representation of a model name
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, serde::Serialize)]
 pub struct Model {
   /// The consumer-recognizable name of the model
   pub name: String,
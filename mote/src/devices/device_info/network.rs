@@ -1,10 +1,11 @@
 use std::fmt::{Display, Formatter, Result};
 
 /// Describes the link-layer technology that supports the network connection.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, serde::Serialize)]
 pub enum NetworkType {
   WiFi,
   Ethernet,
+  #[default]
   Unknown
 }
 
@@ -32,7 +33,7 @@ impl From<&str> for NetworkType {
 }
 
 /// Describes the network this device is connected to
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, serde::Serialize)]
 pub struct Network {
   pub network_type: NetworkType,
   pub network_name: String,
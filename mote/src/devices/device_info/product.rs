@@ -0,0 +1,36 @@
+use std::fmt::{Display, Formatter, Result};
+use super::Model;
+
+/// What kind of hardware the device is.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize)]
+pub enum FormFactor {
+  /// A display with the platform built in
+  Tv,
+
+  /// A box or stick plugged into a display
+  Streamer,
+
+  #[default]
+  Unknown
+}
+
+impl Display for FormFactor {
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+    write!(
+      f, "{}",
+      match self {
+        FormFactor::Tv => "TV",
+        FormFactor::Streamer => "Streaming player",
+        FormFactor::Unknown => "Unknown"
+      }
+    )
+  }
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct Product {
+  pub vendor: String,
+  pub model: Model,
+  pub serial_number: String,
+  pub form_factor: FormFactor
+}
@@ -0,0 +1,47 @@
+use std::fmt::{Display, Formatter, Result};
+use super::Uptime;
+
+/// Whether the device is on, as far as it will say.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub enum PowerMode {
+  On,
+
+  /// Running with the screen off, e.g. a TV playing audio only
+  DisplayOff,
+
+  /// Asleep, but still answering on the network
+  Standby
+}
+
+impl Display for PowerMode {
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+    write!(
+      f, "{}",
+      match self {
+        PowerMode::On => "On",
+        PowerMode::DisplayOff => "Display off",
+        PowerMode::Standby => "Standby"
+      }
+    )
+  }
+}
+
+/// Software and runtime state. Everything but uptime is optional, platforms report what they can.
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct System {
+  pub uptime: Option<Uptime>,
+  pub software_version: Option<String>,
+  pub power_mode: Option<PowerMode>,
+
+  /// Resolution the UI renders at, e.g. `1080p`
+  pub resolution: Option<String>,
+
+  pub time_zone: Option<String>,
+
+  /// Whether sideloading and other developer tooling is enabled
+  pub developer_mode: Option<bool>,
+
+  /// Whether the device only takes some control requests from the network, as Rokus in
+  /// Limited mode do, refusing keypresses
+  pub limited_control: Option<bool>
+}
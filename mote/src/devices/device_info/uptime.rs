@@ -47,4 +47,11 @@ impl Uptime {
       sec_substring
     )
   }
+}
+
+/// Serialized as the uptime in seconds at the moment of serializing
+impl serde::Serialize for Uptime {
+  fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(self.seconds())
+  }
 }
\ No newline at end of file
@@ -0,0 +1,25 @@
+use super::{Key, TvInput};
+
+/// Platform-independent inputs, translated by each backend.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum DeviceInput {
+  KeyPress(Key),
+
+  /// Type text into whatever field has focus
+  Text(String),
+
+  /// Tune the TV's tuner to a channel, by its number
+  Tune(String),
+
+  /// Switch the TV to one of its inputs
+  SwitchInput(TvInput),
+
+  /// Launch an installed app by its platform-specific id
+  Launch(String)
+}
+
+impl From<Key> for DeviceInput {
+  fn from(key: Key) -> DeviceInput {
+    DeviceInput::KeyPress(key)
+  }
+}
@@ -0,0 +1,28 @@
+use std::fmt::{Display, Formatter, Result};
+
+/// The platforms mote can control.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub enum DeviceType {
+  Roku,
+  #[cfg(feature = "samsung")] Samsung,
+  #[cfg(feature = "lg")] Lg,
+  #[cfg(feature = "android")] Android,
+  #[cfg(feature = "cec")] Cec,
+  #[cfg(feature = "kodi")] Kodi
+}
+
+impl Display for DeviceType {
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+    write!(
+      f, "{}",
+      match self {
+        DeviceType::Roku => "Roku",
+        #[cfg(feature = "samsung")] DeviceType::Samsung => "Samsung",
+        #[cfg(feature = "lg")] DeviceType::Lg => "LG webOS",
+        #[cfg(feature = "android")] DeviceType::Android => "Android TV",
+        #[cfg(feature = "cec")] DeviceType::Cec => "HDMI-CEC",
+        #[cfg(feature = "kodi")] DeviceType::Kodi => "Kodi"
+      }
+    )
+  }
+}
@@ -0,0 +1,178 @@
+use std::{collections::HashMap, net::IpAddr, time::Duration};
+use tokio::{select, spawn, sync::mpsc::{unbounded_channel, UnboundedReceiver}, time::timeout};
+use crate::{config::Config, Error};
+use super::{Device, RemoteDevice, roku::RokuDevice};
+use static_init::dynamic;
+
+/// How long a device gets to accept a connection. Devices are on the local network, so one
+/// that is slower than this is unplugged or asleep.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// How long a request may take from start to finish, unless it sets its own
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long a TV's pairing prompt is left for someone to accept it
+pub const PAIRING_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long an idle connection is kept open for the device's next request
+const IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Idle connections kept open per device; keypresses go one at a time, so few are needed
+const IDLE_PER_DEVICE: usize = 2;
+
+/// HTTP client shared by every platform, so connections to each device are pooled and reused
+/// between keypresses rather than opened for each.
+#[dynamic] pub static CLIENT: reqwest::Client =
+  reqwest::Client::builder()
+    .connect_timeout(CONNECT_TIMEOUT)
+    .timeout(REQUEST_TIMEOUT)
+    .pool_idle_timeout(IDLE_TIMEOUT)
+    .pool_max_idle_per_host(IDLE_PER_DEVICE)
+    .tcp_keepalive(IDLE_TIMEOUT)
+    .build()
+    .expect("Failed to build HTTP client");
+
+/// Searches for devices of every supported platform, streaming them as they are found.
+/// Each platform keeps searching until the returned receiver is dropped.
+pub fn discover() -> UnboundedReceiver<Device> {
+  #[allow(unused_mut)]
+  let mut platforms = vec![RokuDevice::discover()];
+  #[cfg(feature = "samsung")] platforms.push(super::samsung::SamsungDevice::discover());
+  #[cfg(feature = "lg")] platforms.push(super::lg::LgDevice::discover());
+  #[cfg(feature = "android")] platforms.push(super::android::AndroidDevice::discover());
+  #[cfg(feature = "cec")] platforms.push(super::cec::CecDevice::discover());
+  #[cfg(feature = "kodi")] platforms.push(super::kodi::KodiDevice::discover());
+
+  merge(platforms)
+}
+
+/// Every device found within `duration`, each once.
+pub async fn discover_for(duration: Duration) -> Vec<Device> {
+  discover_until(duration, |_| false).await
+}
+
+/// Collects devices, each once, until `done` is satisfied with those found so far (it is
+/// asked after each new one) or `within` runs out. Searching stops when this returns.
+pub async fn discover_until(within: Duration, done: impl FnMut(&[Device]) -> bool) -> Vec<Device> {
+  collect_until(discover(), within, done).await
+}
+
+/// Collects devices from `devices` for `discover_until`, telling them apart by `RemoteDevice::id`
+async fn collect_until(mut devices: UnboundedReceiver<Device>, within: Duration, mut done: impl FnMut(&[Device]) -> bool) -> Vec<Device> {
+  let mut found: Vec<Device> = vec![];
+
+  let _ = timeout(within, async {
+    while let Some(device) = devices.recv().await {
+      let id = device.id();
+      if found.iter().any(|known| known.id() == id) { continue }
+
+      found.push(device);
+      if done(&found) { break }
+    }
+  }).await;
+
+  found
+}
+
+/// Finds the device `selector` names, returning as soon as it is found: an alias from the
+/// config, or a device's name, serial number or IP address. Fails if it is not found `within`.
+pub async fn find_device(selector: &str, within: Duration) -> crate::Result<Device> {
+  let aliases = Config::load().unwrap_or_default().aliases;
+  let target = resolve_alias(selector, &aliases);
+
+  discover_until(within, |found| found.last().is_some_and(|device| matches(device, target)))
+    .await
+    .pop()
+    .filter(|device| matches(device, target))
+    .ok_or_else(|| Error::NotFound { selector: selector.into(), seconds: within.as_secs() })
+}
+
+/// What `selector` stands for, if it is an alias
+fn resolve_alias<'a>(selector: &'a str, aliases: &'a HashMap<String, String>) -> &'a str {
+  aliases.get(selector).map(String::as_str).unwrap_or(selector)
+}
+
+/// Whether `selector` is the device's name (ignoring case), serial number or IP address
+fn matches(device: &Device, selector: &str) -> bool {
+  let info = device.info();
+
+  info.name.eq_ignore_ascii_case(selector)
+    || info.product.serial_number.eq_ignore_ascii_case(selector)
+    || selector.parse::<IpAddr>().is_ok_and(|ip| device.location().is_some_and(|location| location.ip() == ip))
+}
+
+/// Streams devices from several sources through one receiver. Dropping it stops every source.
+pub fn merge(sources: Vec<UnboundedReceiver<Device>>) -> UnboundedReceiver<Device> {
+  let (tx, rx) = unbounded_channel::<Device>();
+
+  for mut source in sources {
+    let tx = tx.clone();
+    spawn(async move {
+      loop {
+        select! {
+          // dropping the source's receiver tells it to stop searching
+          _ = tx.closed() => break,
+          device = source.recv() => match device {
+            Some(device) => if tx.send(device).is_err() { break },
+            None => break
+          }
+        }
+      }
+    });
+  }
+
+  rx
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{net::SocketAddr, sync::Arc};
+  use futures::future::BoxFuture;
+  use crate::devices::{Capabilities, DeviceInfo, DeviceInput, DeviceType};
+  use super::*;
+
+  /// A device that reports no serial number, like Kodi or CEC devices
+  #[derive(Debug)]
+  struct Serialless { location: SocketAddr, info: DeviceInfo }
+
+  impl RemoteDevice for Serialless {
+    fn discover() -> UnboundedReceiver<Device> { unbounded_channel().1 }
+    fn device_type(&self) -> DeviceType { DeviceType::Roku }
+    fn location(&self) -> Option<SocketAddr> { Some(self.location) }
+    fn info(&self) -> &DeviceInfo { &self.info }
+    fn capabilities(&self) -> Capabilities { Capabilities::default() }
+
+    fn send_input(&self, _: DeviceInput) -> BoxFuture<'_, crate::Result<()>> {
+      Box::pin(async move { Ok(()) })
+    }
+
+    fn query_state(&self) -> BoxFuture<'_, crate::Result<Device>> {
+      Box::pin(async move { Err(Error::Timeout) })
+    }
+  }
+
+  fn serialless(address: &str) -> Device {
+    Arc::new(Serialless { location: address.parse().unwrap(), info: DeviceInfo::default() })
+  }
+
+  #[tokio::test]
+  async fn keeps_devices_without_serial_numbers_apart() {
+    let (tx, rx) = unbounded_channel();
+    for address in ["192.168.1.20:8060", "192.168.1.21:8060", "192.168.1.20:8060"] {
+      tx.send(serialless(address)).unwrap();
+    }
+    drop(tx);
+
+    let found = collect_until(rx, Duration::from_secs(1), |_| false).await;
+    let ids: Vec<_> = found.iter().map(|device| device.id()).collect();
+    assert_eq!(ids, ["Roku@192.168.1.20", "Roku@192.168.1.21"]);
+  }
+
+  #[test]
+  fn resolves_aliases() {
+    let aliases = [("den".to_owned(), "192.168.1.40".to_owned())].into();
+
+    assert_eq!(resolve_alias("den", &aliases), "192.168.1.40");
+    assert_eq!(resolve_alias("Living Room", &aliases), "Living Room");
+  }
+}
@@ -0,0 +1,25 @@
+/// Platform-independent remote control keys. The UI speaks only these; each backend maps
+/// them onto whatever its device understands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum Key {
+  Power,
+  Home,
+
+  Back,
+  Ok,
+
+  Up,
+  Down,
+  Left,
+  Right,
+
+  InstantReplay,
+  Info,
+
+  VolumeUp,
+  VolumeDown,
+  VolumeMute,
+
+  /// Ring the remote, on devices whose remote has a speaker
+  FindRemote
+}
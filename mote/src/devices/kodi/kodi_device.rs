@@ -0,0 +1,189 @@
+use std::{net::SocketAddr, sync::Arc};
+use anyhow::{anyhow, bail};
+use futures::{future::BoxFuture, TryFutureExt};
+use serde_json::{json, Value};
+use tokio::sync::mpsc::UnboundedReceiver;
+use crate::devices::{
+  App, Capabilities, Capability, Device, DeviceInfo, DeviceInput, DeviceType, FormFactor, Model, Network, NetworkType,
+  Product, RemoteDevice, System
+};
+use super::{kodi_discovery, kodi_request, KodiRpc};
+
+/// Default port of Kodi's web server, which carries JSON-RPC
+pub const JSONRPC_PORT: u16 = 8080;
+
+/// A Kodi media center, controlled over JSON-RPC.
+#[derive(Debug, Clone)]
+pub struct KodiDevice {
+  location: SocketAddr,
+  info: DeviceInfo,
+  rpc: KodiRpc
+}
+
+impl KodiDevice {
+  /// Connects to the web server at `address` (`host:port`) and reads device info.
+  pub async fn query(address: &str, username: Option<String>, password: Option<String>) -> anyhow::Result<Self> {
+    let location = tokio::net::lookup_host(address)
+      .await?
+      .next()
+      .ok_or_else(|| anyhow!("{} does not resolve to an address", address))?;
+
+    let rpc = KodiRpc::new(&format!("http://{}", location), username, password);
+    let info = Self::device_info(&rpc, location).await?;
+
+    Ok(KodiDevice { location, info, rpc })
+  }
+
+  async fn device_info(rpc: &KodiRpc, location: SocketAddr) -> anyhow::Result<DeviceInfo> {
+    let labels = rpc.call("XBMC.GetInfoLabels", json!({
+      "labels": ["System.FriendlyName", "System.BuildVersion", "System.OSVersionInfo", "Network.MacAddress"]
+    })).await?;
+    let label = |name: &str| labels[name].as_str().unwrap_or_default().to_owned();
+
+    // Kodi answers `Busy` while it is still looking a label up
+    let mac_address = Some(label("Network.MacAddress")).filter(|mac| mac.contains(':')).unwrap_or_default();
+
+    Ok(DeviceInfo {
+      name: label("System.FriendlyName"),
+      product: Product {
+        vendor: "Team Kodi".into(),
+        // the MAC address identifies the machine whether it was found over SSDP or configured by hand
+        serial_number: if mac_address.is_empty() { location.to_string() } else { mac_address.clone() },
+        model: Model {
+          name: "Kodi".into(),
+          alternate_name: label("System.OSVersionInfo"),
+          number: String::new()
+        },
+        form_factor: FormFactor::Streamer
+      },
+      network: Network {
+        network_type: NetworkType::Unknown,
+        network_name: String::new(),
+        mac_address
+      },
+      system: System {
+        software_version: Some(label("System.BuildVersion")),
+        ..System::default()
+      },
+      now_playing: Self::now_playing(rpc).await?,
+      features: vec![]
+    })
+  }
+
+  /// What the first active player is playing, if anything.
+  async fn now_playing(rpc: &KodiRpc) -> anyhow::Result<Option<String>> {
+    let players = rpc.call("Player.GetActivePlayers", json!({})).await?;
+    let Some(player_id) = players[0]["playerid"].as_i64() else { return Ok(None) };
+
+    let item = rpc.call("Player.GetItem", json!({
+      "playerid": player_id,
+      "properties": ["title", "showtitle", "season", "episode", "artist"]
+    })).await?;
+
+    Ok(describe_item(&item["item"]))
+  }
+}
+
+/// A one-line description of a `Player.GetItem` item: `Show S01E02 · Title` for episodes,
+/// `Artist – Title` for songs, otherwise the title.
+pub fn describe_item(item: &Value) -> Option<String> {
+  let title = item["title"].as_str().filter(|t| !t.is_empty()).or(item["label"].as_str())?;
+
+  Some(match item["type"].as_str() {
+    Some("episode") => format!(
+      "{} S{:02}E{:02} · {}",
+      item["showtitle"].as_str().unwrap_or_default(),
+      item["season"].as_i64().unwrap_or_default(),
+      item["episode"].as_i64().unwrap_or_default(),
+      title
+    ),
+    Some("song") => match item["artist"].as_array().and_then(|a| a.first()).and_then(Value::as_str) {
+      Some(artist) => format!("{} – {}", artist, title),
+      None => title.to_owned()
+    },
+    _ => title.to_owned()
+  })
+}
+
+impl RemoteDevice for KodiDevice {
+  fn discover() -> UnboundedReceiver<Device> { kodi_discovery::discover() }
+
+  fn device_type(&self) -> DeviceType { DeviceType::Kodi }
+
+  fn location(&self) -> Option<SocketAddr> { Some(self.location) }
+
+  fn info(&self) -> &DeviceInfo { &self.info }
+
+  fn capabilities(&self) -> Capabilities {
+    Capabilities::from([
+      Capability::Power, Capability::Volume, Capability::InstantReplay, Capability::Info, Capability::Text, Capability::Apps
+    ])
+  }
+
+  fn send_input(&self, input: DeviceInput) -> BoxFuture<'_, crate::Result<()>> {
+    Box::pin(async move {
+      let (method, params) = match input {
+        DeviceInput::KeyPress(key) => kodi_request(key).ok_or_else(|| self.unsupported_key(key))?,
+        DeviceInput::Text(text) => ("Input.SendText", json!({ "text": text, "done": true })),
+        DeviceInput::Tune(_) | DeviceInput::SwitchInput(_) => bail!("Kodi cannot tune channels or switch inputs from mote"),
+        DeviceInput::Launch(addon_id) => ("Addons.ExecuteAddon", json!({ "addonid": addon_id }))
+      };
+
+      self.rpc.call(method, params).await?;
+      Ok(())
+    }.err_into())
+  }
+
+  fn query_state(&self) -> BoxFuture<'_, crate::Result<Device>> {
+    Box::pin(async move {
+      let mut info = Self::device_info(&self.rpc, self.location).await?;
+
+      // keep the identity it was found with, even if the MAC address has turned up (or gone `Busy`) since
+      info.product.serial_number = self.info.product.serial_number.clone();
+      Ok(Arc::new(KodiDevice { info, ..self.clone() }) as Device)
+    })
+  }
+
+  /// Enabled plugin add-ons, which are what Kodi users think of as apps
+  fn apps(&self) -> BoxFuture<'_, anyhow::Result<Vec<App>>> {
+    Box::pin(async move {
+      let result = self.rpc.call("Addons.GetAddons", json!({
+        "type": "xbmc.python.pluginsource",
+        "enabled": true,
+        "properties": ["name"]
+      })).await?;
+
+      Ok(
+        result["addons"]
+          .as_array()
+          .map(Vec::as_slice)
+          .unwrap_or_default()
+          .iter()
+          .filter_map(|addon| Some(App {
+            id: addon["addonid"].as_str()?.to_owned(),
+            name: addon["name"].as_str()?.to_owned()
+          }))
+          .collect()
+      )
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn describes_playing_items() {
+    let episode = json!({ "type": "episode", "title": "Pilot", "showtitle": "Twin Peaks", "season": 1, "episode": 1, "label": "Pilot" });
+    assert_eq!(describe_item(&episode).as_deref(), Some("Twin Peaks S01E01 · Pilot"));
+
+    let song = json!({ "type": "song", "title": "Windowlicker", "artist": ["Aphex Twin"] });
+    assert_eq!(describe_item(&song).as_deref(), Some("Aphex Twin – Windowlicker"));
+
+    let channel = json!({ "type": "channel", "title": "", "label": "BBC One" });
+    assert_eq!(describe_item(&channel).as_deref(), Some("BBC One"));
+
+    assert_eq!(describe_item(&json!({})), None);
+  }
+}
@@ -0,0 +1,21 @@
+use std::{net::SocketAddr, sync::Arc};
+use tokio::sync::mpsc::UnboundedReceiver;
+use crate::{config::Config, devices::{Device, discovery::merge, manual, ssdp_search}};
+use super::{KodiDevice, JSONRPC_PORT};
+
+/// Finds Kodi instances advertising a UPnP renderer over SSDP, and connects to those listed
+/// under `[[kodi]]` in the configuration file, until the returned receiver is dropped.
+pub fn discover() -> UnboundedReceiver<Device> {
+  // plenty of other renderers answer this search; only Kodi answers JSON-RPC, the rest are skipped
+  let found = ssdp_search::search("urn:schemas-upnp-org:device:MediaRenderer:1", |responder| async move {
+    let address = SocketAddr::new(responder.addr.ip(), JSONRPC_PORT).to_string();
+    Ok(Arc::new(KodiDevice::query(&address, None, None).await?) as Device)
+  });
+
+  let configured = manual::connect_all(Config::load().unwrap_or_default().kodi, |device| async move {
+    let address = device.address_with_port(JSONRPC_PORT);
+    Ok(Arc::new(KodiDevice::query(&address, device.username, device.password).await?) as Device)
+  });
+
+  merge(vec![found, configured])
+}
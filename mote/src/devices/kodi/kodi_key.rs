@@ -0,0 +1,23 @@
+use serde_json::{json, Value};
+use crate::devices::Key;
+
+/// Maps mote's keys onto the JSON-RPC method (and parameters) Kodi's own remotes use for them.
+pub fn kodi_request(key: Key) -> Option<(&'static str, Value)> {
+  Some(match key {
+    // Kodi runs on a computer, so power brings up the shutdown menu rather than cutting power
+    Key::Power         => ("GUI.ActivateWindow", json!({ "window": "shutdownmenu" })),
+    Key::Home          => ("Input.Home", json!({})),
+    Key::Back          => ("Input.Back", json!({})),
+    Key::Ok            => ("Input.Select", json!({})),
+    Key::Up            => ("Input.Up", json!({})),
+    Key::Down          => ("Input.Down", json!({})),
+    Key::Left          => ("Input.Left", json!({})),
+    Key::Right         => ("Input.Right", json!({})),
+    Key::InstantReplay => ("Input.ExecuteAction", json!({ "action": "stepback" })),
+    Key::Info          => ("Input.Info", json!({})),
+    Key::VolumeUp      => ("Application.SetVolume", json!({ "volume": "increment" })),
+    Key::VolumeDown    => ("Application.SetVolume", json!({ "volume": "decrement" })),
+    Key::VolumeMute    => ("Application.SetMute", json!({ "mute": "toggle" })),
+    Key::FindRemote    => return None
+  })
+}
@@ -0,0 +1,106 @@
+use anyhow::bail;
+use serde_json::{json, Value};
+use crate::devices::discovery::CLIENT;
+
+/// A client for Kodi's JSON-RPC API over HTTP.
+#[derive(Debug, Clone)]
+pub struct KodiRpc {
+  /// The `/jsonrpc` endpoint
+  url: String,
+
+  /// Credentials for Kodi's web server, when it requires them
+  username: Option<String>,
+  password: Option<String>
+}
+
+impl KodiRpc {
+  /// `endpoint` is the web server's base URL, e.g. `http://192.168.1.20:8080`.
+  pub fn new(endpoint: &str, username: Option<String>, password: Option<String>) -> Self {
+    KodiRpc { url: format!("{}/jsonrpc", endpoint), username, password }
+  }
+
+  /// Calls `method`, returning its result or the error Kodi answered with.
+  pub async fn call(&self, method: &str, params: Value) -> anyhow::Result<Value> {
+    let body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+
+    let mut request = CLIENT
+      .post(&self.url)
+      .header("Content-Type", "application/json")
+      .body(body.to_string());
+
+    if let Some(username) = &self.username {
+      request = request.basic_auth(username, self.password.as_ref());
+    }
+
+    let text = request.send().await?.error_for_status()?.text().await?;
+    let mut response: Value = serde_json::from_str(&text)?;
+
+    if let Some(error) = response.get("error") {
+      bail!("Kodi rejected {}: {}", method, error["message"].as_str().unwrap_or("unknown error"))
+    }
+
+    Ok(response["result"].take())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use tokio::{io::{AsyncReadExt, AsyncWriteExt}, net::TcpListener};
+
+  /// Stands in for Kodi's web server: answers one request with `body` and reports the request.
+  async fn stand_in(body: &'static str) -> (String, tokio::task::JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let endpoint = format!("http://{}", listener.local_addr().unwrap());
+
+    let kodi = tokio::spawn(async move {
+      let (mut stream, _) = listener.accept().await.unwrap();
+
+      // read the headers, then as much body as they announce
+      let mut request = vec![];
+      let mut buffer = [0; 4096];
+      loop {
+        let read = stream.read(&mut buffer).await.unwrap();
+        request.extend_from_slice(&buffer[..read]);
+
+        let text = String::from_utf8_lossy(&request);
+        let Some((headers, body)) = text.split_once("\r\n\r\n") else { continue };
+        let length = headers
+          .lines()
+          .find_map(|line| line.to_ascii_lowercase().strip_prefix("content-length:").map(|l| l.trim().parse::<usize>().unwrap()))
+          .unwrap_or(0);
+
+        if body.len() >= length || read == 0 { break }
+      }
+
+      let response = format!("HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}", body.len(), body);
+      stream.write_all(response.as_bytes()).await.unwrap();
+
+      String::from_utf8_lossy(&request).into_owned()
+    });
+
+    (endpoint, kodi)
+  }
+
+  #[tokio::test]
+  async fn returns_results() {
+    let (endpoint, kodi) = stand_in(r#"{"id":1,"jsonrpc":"2.0","result":"OK"}"#).await;
+
+    let rpc = KodiRpc::new(&endpoint, Some("kodi".into()), Some("secret".into()));
+    assert_eq!(rpc.call("Input.Up", json!({})).await.unwrap(), "OK");
+
+    let request = kodi.await.unwrap();
+    assert!(request.starts_with("POST /jsonrpc "));
+    assert!(request.contains(r#""method":"Input.Up""#));
+    // base64 of `kodi:secret`
+    assert!(request.contains("a29kaTpzZWNyZXQ="));
+  }
+
+  #[tokio::test]
+  async fn reports_errors() {
+    let (endpoint, _) = stand_in(r#"{"id":1,"jsonrpc":"2.0","error":{"code":-32601,"message":"Method not found."}}"#).await;
+
+    let error = KodiRpc::new(&endpoint, None, None).call("Input.Nope", json!({})).await.unwrap_err();
+    assert_eq!(error.to_string(), "Kodi rejected Input.Nope: Method not found.");
+  }
+}
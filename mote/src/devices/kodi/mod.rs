@@ -0,0 +1,8 @@
+mod kodi_device;
+mod kodi_discovery;
+mod kodi_key;
+mod kodi_rpc;
+
+pub use kodi_device::*;
+pub use kodi_key::*;
+pub use kodi_rpc::*;
@@ -0,0 +1,120 @@
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+use futures::{future::BoxFuture, TryFutureExt};
+use tokio::sync::mpsc::UnboundedReceiver;
+use crate::devices::{
+  App, Capabilities, Capability, Device, DeviceInfo, DeviceInput, DeviceType, FormFactor, Model, Network, NetworkType,
+  Product, RemoteDevice, System,
+  discovery::{CLIENT, PAIRING_TIMEOUT, REQUEST_TIMEOUT}, token_store::TokenStore
+};
+use super::{lg_discovery, LgDeviceInfo, LgKey, LgRemote};
+
+/// Port of the TLS SSAP endpoint, the only one current webOS firmware accepts
+const SSAP_PORT: u16 = 3001;
+
+/// An LG webOS TV, controlled over SSAP.
+#[derive(Debug, Clone)]
+pub struct LgDevice {
+  location: SocketAddr,
+
+  /// URL of the UPnP description device info is read from
+  description: String,
+
+  info: DeviceInfo,
+
+  /// Shared between refreshed copies of the device, so polling never drops the session
+  remote: Arc<LgRemote>
+}
+
+impl LgDevice {
+  /// Reads device info from the TV's UPnP description and prepares (but does not open) the SSAP session.
+  pub async fn query(location: SocketAddr, description: String) -> anyhow::Result<Self> {
+    let info = Self::device_info(&description).await?;
+    let remote = LgRemote::new(&format!("wss://{}:{}", location.ip(), SSAP_PORT), &info.product.serial_number, TokenStore::new("lg"));
+
+    let mut location = location; location.set_port(SSAP_PORT);
+
+    Ok(LgDevice { location, description, info, remote: Arc::new(remote) })
+  }
+
+  async fn device_info(description: &str) -> anyhow::Result<DeviceInfo> {
+    let response =
+      CLIENT
+        .get(description)
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+
+    let LgDeviceInfo { device } = serde_xml_rs::from_str(&response)?;
+
+    Ok(DeviceInfo {
+      name: device.name,
+      product: Product {
+        vendor: device.vendor_name,
+        // webOS does not expose a serial number without pairing, the UDN is the stable identifier
+        serial_number: device.udn,
+        model: Model {
+          number: device.model_number.unwrap_or_else(|| device.model_name.clone()),
+          alternate_name: device.model_name.clone(),
+          name: device.model_name
+        },
+        form_factor: FormFactor::Tv
+      },
+      network: Network {
+        network_type: NetworkType::Unknown,
+        network_name: String::new(),
+        mac_address: String::new()
+      },
+      system: System::default(),
+      now_playing: None,
+      features: vec![]
+    })
+  }
+}
+
+impl RemoteDevice for LgDevice {
+  fn discover() -> UnboundedReceiver<Device> { lg_discovery::discover() }
+
+  fn device_type(&self) -> DeviceType { DeviceType::Lg }
+
+  fn location(&self) -> Option<SocketAddr> { Some(self.location) }
+
+  fn info(&self) -> &DeviceInfo { &self.info }
+
+  fn capabilities(&self) -> Capabilities {
+    Capabilities::from([
+      Capability::Power, Capability::Volume, Capability::InstantReplay, Capability::Info, Capability::Text, Capability::Apps
+    ])
+  }
+
+  fn send_input(&self, input: DeviceInput) -> BoxFuture<'_, crate::Result<()>> {
+    Box::pin(async move {
+      match input {
+        DeviceInput::KeyPress(key) => match LgKey::for_key(key).ok_or_else(|| self.unsupported_key(key))? {
+          LgKey::Button(name) => self.remote.button(name).await,
+          LgKey::Request(uri) => self.remote.request(uri, None).await.map(|_| ()),
+          LgKey::ToggleMute => self.remote.toggle_mute().await
+        },
+        DeviceInput::Text(text) => self.remote.insert_text(&text).await,
+        DeviceInput::Tune(_) | DeviceInput::SwitchInput(_) => Err(anyhow::anyhow!("LG TVs cannot tune channels or switch inputs from mote")),
+        DeviceInput::Launch(app_id) => self.remote.launch(&app_id).await
+      }
+    }.err_into())
+  }
+
+  fn input_timeout(&self) -> Duration {
+    if self.remote.is_paired() { REQUEST_TIMEOUT } else { PAIRING_TIMEOUT }
+  }
+
+  fn query_state(&self) -> BoxFuture<'_, crate::Result<Device>> {
+    Box::pin(async move {
+      let info = Self::device_info(&self.description).await?;
+      Ok(Arc::new(LgDevice { info, ..self.clone() }) as Device)
+    })
+  }
+
+  fn apps(&self) -> BoxFuture<'_, anyhow::Result<Vec<App>>> {
+    Box::pin(self.remote.apps())
+  }
+}
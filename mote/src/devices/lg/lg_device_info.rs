@@ -0,0 +1,27 @@
+/// LG-specific representation of device info: the UPnP description the TV links to from its
+/// SSDP response. Unlike the SSAP API it can be read without pairing.
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(rename="root")]
+pub struct LgDeviceInfo {
+  pub device: LgDeviceDescription
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct LgDeviceDescription {
+  /// Human-readable device name
+  #[serde(rename="friendlyName")]
+  pub name: String,
+
+  #[serde(rename="manufacturer")]
+  pub vendor_name: String,
+
+  #[serde(rename="modelName")]
+  pub model_name: String,
+
+  #[serde(rename="modelNumber")]
+  pub model_number: Option<String>,
+
+  /// Unique device name, stable across IP changes
+  #[serde(rename="UDN")]
+  pub udn: String
+}
@@ -0,0 +1,13 @@
+use std::sync::Arc;
+use anyhow::anyhow;
+use tokio::sync::mpsc::UnboundedReceiver;
+use crate::devices::{Device, ssdp_search};
+use super::LgDevice;
+
+/// Searches for LG webOS TVs over SSDP until the returned receiver is dropped.
+pub fn discover() -> UnboundedReceiver<Device> {
+  ssdp_search::search("urn:lge-com:service:webos-second-screen:1", |responder| async move {
+    let description = responder.location.ok_or_else(|| anyhow!("webOS TV did not advertise a description"))?;
+    Ok(Arc::new(LgDevice::query(responder.addr, description).await?) as Device)
+  })
+}
@@ -0,0 +1,36 @@
+use crate::devices::Key;
+
+/// How a key is delivered to a webOS TV
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LgKey {
+  /// A button on the pointer input socket
+  Button(&'static str),
+
+  /// An SSAP request without payload
+  Request(&'static str),
+
+  /// Mute has no toggle request, so it needs the current state first
+  ToggleMute
+}
+
+impl LgKey {
+  /// How `key` is delivered, if webOS has an equivalent
+  pub fn for_key(key: Key) -> Option<LgKey> {
+    Some(match key {
+      Key::Power         => LgKey::Request("ssap://system/turnOff"),
+      Key::Home          => LgKey::Button("HOME"),
+      Key::Back          => LgKey::Button("BACK"),
+      Key::Ok            => LgKey::Button("ENTER"),
+      Key::Up            => LgKey::Button("UP"),
+      Key::Down          => LgKey::Button("DOWN"),
+      Key::Left          => LgKey::Button("LEFT"),
+      Key::Right         => LgKey::Button("RIGHT"),
+      Key::InstantReplay => LgKey::Button("REWIND"),
+      Key::Info          => LgKey::Button("INFO"),
+      Key::VolumeUp      => LgKey::Request("ssap://audio/volumeUp"),
+      Key::VolumeDown    => LgKey::Request("ssap://audio/volumeDown"),
+      Key::VolumeMute    => LgKey::ToggleMute,
+      Key::FindRemote    => return None
+    })
+  }
+}
@@ -0,0 +1,416 @@
+use std::fmt::{Debug, Formatter};
+use anyhow::anyhow;
+use futures::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use tokio::{net::TcpStream, sync::Mutex, time::timeout};
+use tokio_tungstenite::{connect_async_tls_with_config, tungstenite::{self, Message}, Connector, MaybeTlsStream, WebSocketStream};
+use crate::{devices::{discovery::PAIRING_TIMEOUT, token_store::TokenStore, App}, Error};
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// What mote asks to be allowed to do when pairing
+const PERMISSIONS: [&str; 12] = [
+  "LAUNCH",
+  "LAUNCH_WEBAPP",
+  "CONTROL_AUDIO",
+  "CONTROL_DISPLAY",
+  "CONTROL_INPUT_MEDIA_PLAYBACK",
+  "CONTROL_INPUT_TV",
+  "CONTROL_POWER",
+  "CONTROL_MOUSE_AND_KEYBOARD",
+  "CONTROL_INPUT_TEXT",
+  "READ_APP_STATUS",
+  "READ_INSTALLED_APPS",
+  "READ_POWER_STATE"
+];
+
+/// Opens a WebSocket, accepting the self-signed certificates webOS TVs serve.
+async fn open(url: &str) -> anyhow::Result<Socket> {
+  let tls = native_tls::TlsConnector::builder().danger_accept_invalid_certs(true).build()?;
+  let (socket, _) = connect_async_tls_with_config(url, None, false, Some(Connector::NativeTls(tls))).await?;
+  Ok(socket)
+}
+
+/// A registered SSAP session, plus the pointer input socket once one has been needed
+struct Session {
+  socket: Socket,
+  pointer: Option<Socket>,
+  next_id: u64
+}
+
+impl Session {
+  /// Sends one SSAP request and waits for its response payload.
+  async fn request(&mut self, uri: &str, payload: Option<Value>) -> anyhow::Result<Value> {
+    let id = self.next_id.to_string();
+    self.next_id += 1;
+
+    let mut message = json!({ "type": "request", "id": id, "uri": uri });
+    if let Some(payload) = payload { message["payload"] = payload; }
+    self.socket.send(Message::Text(message.to_string())).await?;
+
+    while let Some(message) = self.socket.next().await {
+      let Message::Text(text) = message? else { continue };
+      let response: Value = serde_json::from_str(&text)?;
+      if response["id"] != id.as_str() { continue }
+
+      return match response["type"].as_str() {
+        // e.g. `401 insufficient permissions`, for requests the client key was not granted
+        Some("error") if response["error"].as_str().is_some_and(|e| e.starts_with("401")) => Err(Error::Permission(format!("{}: {}", uri, response["error"])).into()),
+        Some("error") => Err(anyhow!("TV refused {}: {}", uri, response["error"])),
+        _ => Ok(response["payload"].clone())
+      }
+    }
+
+    Err(tungstenite::Error::ConnectionClosed.into())
+  }
+
+  /// Presses a button on the pointer input socket, opening the socket first if needed.
+  async fn button(&mut self, name: &str) -> anyhow::Result<()> {
+    if self.pointer.is_none() {
+      let response = self.request("ssap://com.webos.service.networkinput/getPointerInputSocket", None).await?;
+      let path = response["socketPath"].as_str().ok_or_else(|| anyhow!("TV did not provide a pointer input socket"))?;
+      self.pointer = Some(open(path).await?);
+    }
+
+    let pointer = self.pointer.as_mut().unwrap();
+    if let Err(e) = pointer.send(Message::Text(format!("type:button\nname:{}\n\n", name))).await {
+      self.pointer = None;
+      return Err(e.into())
+    }
+
+    Ok(())
+  }
+}
+
+/// Connection to an LG webOS TV over SSAP, its WebSocket "second screen" protocol.
+///
+/// The session is opened lazily on the first key and kept for the following ones. The first
+/// time mote registers, the TV prompts the user to allow it and issues a client key, which is
+/// stored so later registrations go straight through.
+pub struct LgRemote {
+  /// e.g. `wss://192.168.1.30:3001`
+  endpoint: String,
+
+  /// Which TV the stored client key belongs to
+  device_id: String,
+
+  keys: TokenStore,
+  session: Mutex<Option<Session>>
+}
+
+impl Debug for LgRemote {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("LgRemote").field("endpoint", &self.endpoint).field("device_id", &self.device_id).finish()
+  }
+}
+
+impl LgRemote {
+  pub fn new(endpoint: &str, device_id: &str, keys: TokenStore) -> Self {
+    LgRemote {
+      endpoint: endpoint.to_owned(),
+      device_id: device_id.to_owned(),
+      keys,
+      session: Mutex::new(None)
+    }
+  }
+
+  /// Whether the TV has issued a client key, so registering will not wait on its prompt
+  pub fn is_paired(&self) -> bool { self.keys.get(&self.device_id).is_some() }
+
+  /// Sends an SSAP request (e.g. `ssap://audio/volumeUp`), returning the response payload.
+  pub async fn request(&self, uri: &str, payload: Option<Value>) -> anyhow::Result<Value> {
+    let mut session = self.session.lock().await;
+
+    // the TV drops idle sessions, so a kept session may fail once on transport; reconnect and retry
+    if let Some(open) = session.as_mut() {
+      match open.request(uri, payload.clone()).await {
+        Err(e) if e.is::<tungstenite::Error>() => (),
+        result => return result
+      }
+    }
+
+    let mut fresh = self.register().await?;
+    let result = fresh.request(uri, payload).await;
+    *session = Some(fresh);
+    result
+  }
+
+  /// Presses a pointer socket button (e.g. `UP`, `ENTER`, `HOME`).
+  pub async fn button(&self, name: &str) -> anyhow::Result<()> {
+    let mut session = self.session.lock().await;
+
+    if let Some(open) = session.as_mut() {
+      match open.button(name).await {
+        Err(e) if e.is::<tungstenite::Error>() => (),
+        result => return result
+      }
+    }
+
+    let mut fresh = self.register().await?;
+    let result = fresh.button(name).await;
+    *session = Some(fresh);
+    result
+  }
+
+  /// Flips mute, which SSAP only offers as "set", based on the current audio status.
+  pub async fn toggle_mute(&self) -> anyhow::Result<()> {
+    let status = self.request("ssap://audio/getStatus", None).await?;
+    let muted = status["mute"].as_bool().unwrap_or(false);
+    self.request("ssap://audio/setMute", Some(json!({ "mute": !muted }))).await?;
+    Ok(())
+  }
+
+  /// Types text into the focused field through the on-screen keyboard service.
+  pub async fn insert_text(&self, text: &str) -> anyhow::Result<()> {
+    self.request("ssap://com.webos.service.ime/insertText", Some(json!({ "text": text, "replace": 0 }))).await?;
+    Ok(())
+  }
+
+  /// Launches an installed app by its id (e.g. `netflix`).
+  pub async fn launch(&self, app_id: &str) -> anyhow::Result<()> {
+    self.request("ssap://system.launcher/launch", Some(json!({ "id": app_id }))).await?;
+    Ok(())
+  }
+
+  /// Lists the installed apps the home launcher shows.
+  pub async fn apps(&self) -> anyhow::Result<Vec<App>> {
+    let listed = self.request("ssap://com.webos.applicationManager/listApps", None).await?;
+
+    Ok(
+      listed["apps"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        // system services are listed too, but hidden from the launcher
+        .filter(|app| app["visible"].as_bool() != Some(false))
+        .filter_map(|app| Some(App {
+          id: app["id"].as_str()?.to_owned(),
+          name: app["title"].as_str()?.to_owned()
+        }))
+        .collect()
+    )
+  }
+
+  /// Opens a session and registers, waiting for the user to accept the prompt if we have no client key yet.
+  async fn register(&self) -> anyhow::Result<Session> {
+    let mut socket = open(&self.endpoint).await?;
+
+    let mut payload = json!({
+      "forcePairing": false,
+      "pairingType": "PROMPT",
+      "manifest": {
+        "manifestVersion": 1,
+        "appVersion": env!("CARGO_PKG_VERSION"),
+        "permissions": PERMISSIONS
+      }
+    });
+    if let Some(key) = self.keys.get(&self.device_id) { payload["client-key"] = key.into(); }
+
+    let register = json!({ "type": "register", "id": "register_0", "payload": payload });
+    socket.send(Message::Text(register.to_string())).await?;
+
+    // nobody may be in front of the TV to answer its prompt
+    match timeout(PAIRING_TIMEOUT, self.registered(&mut socket)).await {
+      Ok(registered) => registered.map(|()| Session { socket, pointer: None, next_id: 1 }),
+      Err(_) => Err(Error::Timeout.into())
+    }
+  }
+
+  /// Waits for the TV to answer a registration, storing any client key it issues.
+  async fn registered(&self, socket: &mut Socket) -> anyhow::Result<()> {
+    while let Some(message) = socket.next().await {
+      let Message::Text(text) = message? else { continue };
+      let response: Value = serde_json::from_str(&text)?;
+
+      match response["type"].as_str() {
+        Some("registered") => {
+          if let Some(key) = response["payload"]["client-key"].as_str() {
+            self.keys.set(&self.device_id, key)?;
+          }
+          return Ok(())
+        },
+        // also what a stored client key the TV no longer accepts gets
+        Some("error") => return Err(Error::Permission(format!("the TV did not allow mote ({}), accept the prompt on screen", response["error"])).into()),
+
+        // "response" with a PROMPT pairing type means the prompt is showing, keep waiting
+        _ => continue
+      }
+    }
+
+    Err(anyhow!("TV closed the connection while pairing"))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::path::PathBuf;
+  use tokio::{net::TcpListener, sync::mpsc::{unbounded_channel, UnboundedReceiver}};
+  use tokio_tungstenite::accept_async;
+
+  fn temporary_store(name: &str) -> (TokenStore, PathBuf) {
+    let path = std::env::temp_dir().join(format!("mote-test-{}-{}.json", name, std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    (TokenStore::at(path.clone()), path)
+  }
+
+  /// Stands in for a TV: registers one client (issuing `client-key`), answers SSAP requests
+  /// and serves a pointer socket. Everything received, on either socket, is reported.
+  async fn stand_in() -> (String, UnboundedReceiver<String>) {
+    let ssap = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let pointer = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let endpoint = format!("ws://{}", ssap.local_addr().unwrap());
+    let pointer_path = format!("ws://{}/resources/pointer", pointer.local_addr().unwrap());
+    let (tx, rx) = unbounded_channel();
+
+    let pointer_tx = tx.clone();
+    tokio::spawn(async move {
+      let (stream, _) = pointer.accept().await.unwrap();
+      let mut socket = accept_async(stream).await.unwrap();
+      while let Some(Ok(Message::Text(text))) = socket.next().await {
+        let _ = pointer_tx.send(text);
+      }
+    });
+
+    tokio::spawn(async move {
+      let (stream, _) = ssap.accept().await.unwrap();
+      let mut socket = accept_async(stream).await.unwrap();
+      let mut muted = false;
+
+      while let Some(Ok(Message::Text(text))) = socket.next().await {
+        let _ = tx.send(text.clone());
+        let message: Value = serde_json::from_str(&text).unwrap();
+        let id = message["id"].clone();
+
+        let replies = match (message["type"].as_str(), message["uri"].as_str()) {
+          (Some("register"), _) => vec![
+            json!({ "type": "response", "id": id, "payload": { "pairingType": "PROMPT", "returnValue": true } }),
+            json!({ "type": "registered", "id": id, "payload": { "client-key": "abcdef" } })
+          ],
+          (_, Some("ssap://com.webos.service.networkinput/getPointerInputSocket")) => vec![
+            json!({ "type": "response", "id": id, "payload": { "socketPath": pointer_path } })
+          ],
+          (_, Some("ssap://audio/getStatus")) => vec![
+            json!({ "type": "response", "id": id, "payload": { "mute": muted } })
+          ],
+          (_, Some("ssap://audio/setMute")) => {
+            muted = message["payload"]["mute"].as_bool().unwrap();
+            vec![json!({ "type": "response", "id": id, "payload": { "returnValue": true } })]
+          },
+          (_, Some("ssap://com.webos.applicationManager/listApps")) => vec![
+            json!({ "type": "response", "id": id, "payload": { "apps": [
+              { "id": "netflix", "title": "Netflix", "visible": true },
+              { "id": "com.webos.app.softwareupdate", "title": "Software Update", "visible": false }
+            ] } })
+          ],
+          (_, Some("ssap://system/turnOff")) => vec![
+            json!({ "type": "error", "id": id, "error": "401 insufficient permissions" })
+          ],
+          _ => vec![json!({ "type": "response", "id": id, "payload": { "returnValue": true } })]
+        };
+
+        for reply in replies {
+          socket.send(Message::Text(reply.to_string())).await.unwrap();
+        }
+      }
+    });
+
+    (endpoint, rx)
+  }
+
+  #[tokio::test]
+  async fn registers_and_persists_client_key() {
+    let (keys, path) = temporary_store("lg-register");
+    let (endpoint, mut received) = stand_in().await;
+
+    let remote = LgRemote::new(&endpoint, "uuid:tv", keys.clone());
+    remote.request("ssap://audio/volumeUp", None).await.unwrap();
+
+    let register: Value = serde_json::from_str(&received.recv().await.unwrap()).unwrap();
+    assert_eq!(register["type"], "register");
+    assert!(register["payload"]["client-key"].is_null());
+
+    let request: Value = serde_json::from_str(&received.recv().await.unwrap()).unwrap();
+    assert_eq!(request["uri"], "ssap://audio/volumeUp");
+
+    assert_eq!(keys.get("uuid:tv").as_deref(), Some("abcdef"));
+    let _ = std::fs::remove_file(path);
+  }
+
+  #[tokio::test]
+  async fn registers_with_stored_client_key() {
+    let (keys, path) = temporary_store("lg-stored");
+    keys.set("uuid:tv", "stored").unwrap();
+    let (endpoint, mut received) = stand_in().await;
+
+    let remote = LgRemote::new(&endpoint, "uuid:tv", keys);
+    remote.request("ssap://audio/volumeDown", None).await.unwrap();
+
+    let register: Value = serde_json::from_str(&received.recv().await.unwrap()).unwrap();
+    assert_eq!(register["payload"]["client-key"], "stored");
+    let _ = std::fs::remove_file(path);
+  }
+
+  #[tokio::test]
+  async fn sends_buttons_over_pointer_socket() {
+    let (keys, path) = temporary_store("lg-pointer");
+    let (endpoint, mut received) = stand_in().await;
+
+    let remote = LgRemote::new(&endpoint, "uuid:tv", keys);
+    remote.button("UP").await.unwrap();
+    remote.button("ENTER").await.unwrap();
+
+    let received: Vec<String> = [
+      received.recv().await.unwrap(),
+      received.recv().await.unwrap(),
+      received.recv().await.unwrap(),
+      received.recv().await.unwrap()
+    ].into();
+
+    assert!(received.contains(&"type:button\nname:UP\n\n".to_string()));
+    assert!(received.contains(&"type:button\nname:ENTER\n\n".to_string()));
+    let _ = std::fs::remove_file(path);
+  }
+
+  #[tokio::test]
+  async fn toggles_mute_from_current_state() {
+    let (keys, path) = temporary_store("lg-mute");
+    let (endpoint, mut received) = stand_in().await;
+
+    let remote = LgRemote::new(&endpoint, "uuid:tv", keys);
+    remote.toggle_mute().await.unwrap();
+
+    let messages: Vec<Value> = [
+      received.recv().await.unwrap(),
+      received.recv().await.unwrap(),
+      received.recv().await.unwrap()
+    ].iter().map(|m| serde_json::from_str(m).unwrap()).collect();
+
+    assert_eq!(messages[2]["uri"], "ssap://audio/setMute");
+    assert_eq!(messages[2]["payload"]["mute"], true);
+    let _ = std::fs::remove_file(path);
+  }
+
+  #[tokio::test]
+  async fn lists_visible_apps() {
+    let (keys, path) = temporary_store("lg-apps");
+    let (endpoint, _received) = stand_in().await;
+
+    let remote = LgRemote::new(&endpoint, "uuid:tv", keys);
+    let apps = remote.apps().await.unwrap();
+    assert_eq!(apps, vec![App { id: "netflix".into(), name: "Netflix".into() }]);
+    let _ = std::fs::remove_file(path);
+  }
+
+  #[tokio::test]
+  async fn reports_refused_requests() {
+    let (keys, path) = temporary_store("lg-refused");
+    let (endpoint, _received) = stand_in().await;
+
+    let remote = LgRemote::new(&endpoint, "uuid:tv", keys);
+    let error = Error::from(remote.request("ssap://system/turnOff", None).await.unwrap_err());
+    assert!(matches!(&error, Error::Permission(reason) if reason.contains("insufficient permissions")));
+    let _ = std::fs::remove_file(path);
+  }
+}
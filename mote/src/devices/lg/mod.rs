@@ -0,0 +1,10 @@
+mod lg_device;
+mod lg_device_info;
+mod lg_discovery;
+mod lg_key;
+mod lg_remote;
+
+pub use lg_device::*;
+pub use lg_device_info::*;
+pub use lg_key::*;
+pub use lg_remote::*;
@@ -0,0 +1,34 @@
+use std::{fmt::{Display, Formatter, Result}, time::{SystemTime, UNIX_EPOCH}};
+
+/// A line of a device's log output, stamped with when it arrived.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogLine {
+  pub at: SystemTime,
+  pub text: String
+}
+
+impl LogLine {
+  pub fn now(text: String) -> Self { LogLine { at: SystemTime::now(), text } }
+
+  /// Whether the line contains `filter`, ignoring case. An empty filter matches everything.
+  pub fn matches(&self, filter: &str) -> bool {
+    self.text.to_lowercase().contains(&filter.to_lowercase())
+  }
+
+  /// Local time of arrival, as `HH:MM:SS.mmm`
+  pub fn timestamp(&self) -> String {
+    let since_epoch = self.at.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let seconds = since_epoch.as_secs() as libc::time_t;
+
+    let mut local = unsafe { std::mem::zeroed::<libc::tm>() };
+    unsafe { libc::localtime_r(&seconds, &mut local) };
+
+    format!("{:02}:{:02}:{:02}.{:03}", local.tm_hour, local.tm_min, local.tm_sec, since_epoch.subsec_millis())
+  }
+}
+
+impl Display for LogLine {
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+    write!(f, "{} {}", self.timestamp(), self.text)
+  }
+}
@@ -0,0 +1,38 @@
+use std::{future::Future, time::Duration};
+use tokio::{select, spawn, sync::mpsc::{unbounded_channel, UnboundedReceiver}, time::sleep};
+use super::Device;
+
+/// How long to wait before trying an unreachable device again
+const RETRY_RATE: Duration = Duration::from_secs(30);
+
+/// Tries each manually configured target with `query` until it answers (the device may be
+/// off for now), streaming devices as they come up. Stops when the returned receiver is dropped.
+pub fn connect_all<T, F, Fut>(targets: Vec<T>, query: F) -> UnboundedReceiver<Device>
+where
+  T: Clone + Send + 'static,
+  F: Fn(T) -> Fut + Clone + Send + 'static,
+  Fut: Future<Output = anyhow::Result<Device>> + Send + 'static
+{
+  let (tx, rx) = unbounded_channel::<Device>();
+
+  for target in targets {
+    let tx = tx.clone();
+    let query = query.clone();
+
+    spawn(async move {
+      loop {
+        if let Ok(device) = query(target.clone()).await {
+          let _ = tx.send(device);
+          break
+        }
+
+        select! {
+          _ = tx.closed() => break,
+          _ = sleep(RETRY_RATE) => ()
+        }
+      }
+    });
+  }
+
+  rx
+}
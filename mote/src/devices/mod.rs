@@ -0,0 +1,36 @@
+#[cfg(feature = "android")] pub mod android;
+pub mod app;
+pub mod app_state;
+pub mod capabilities;
+pub mod channel;
+#[cfg(feature = "cec")] pub mod cec;
+pub mod device_type;
+pub mod device_input;
+pub mod device_info;
+pub mod discovery; pub use discovery::{discover, discover_for, discover_until, find_device};
+pub mod key;
+pub mod log_line;
+#[cfg(feature = "kodi")] pub mod kodi;
+#[cfg(any(feature = "android", feature = "kodi"))] pub(crate) mod manual;
+#[cfg(feature = "lg")] pub mod lg;
+pub mod remote_device;
+pub mod roku;
+#[cfg(feature = "samsung")] pub mod samsung;
+pub mod send_queue;
+pub(crate) mod ssdp_search;
+#[cfg(any(feature = "samsung", feature = "lg"))] pub(crate) mod token_store;
+
+pub use app::App;
+pub use app_state::{AppState, MediaState};
+pub use capabilities::{Capabilities, Capability};
+pub use channel::{Channel, TvInput};
+pub use device_type::*;
+pub use device_input::*;
+pub use device_info::*;
+pub use key::Key;
+pub use log_line::LogLine;
+pub use remote_device::RemoteDevice;
+pub use send_queue::SendQueue;
+
+/// Shared handle to a device of any platform
+pub type Device = std::sync::Arc<dyn RemoteDevice>;
@@ -0,0 +1,88 @@
+use std::{fmt::Debug, net::SocketAddr, time::Duration};
+use futures::future::BoxFuture;
+use tokio::sync::mpsc::UnboundedReceiver;
+use anyhow::anyhow;
+use super::{discovery::REQUEST_TIMEOUT, App, AppState, Capabilities, Channel, Device, DeviceInfo, DeviceInput, DeviceType, Key, LogLine};
+
+/// Everything the UI needs from a controllable device. Each TV platform implements this,
+/// so adding a platform never means touching `ui::UI`.
+pub trait RemoteDevice: Debug + Send + Sync {
+  /// Starts searching for devices of this platform, streaming them as they are found.
+  fn discover() -> UnboundedReceiver<Device> where Self: Sized;
+
+  /// Which platform this device belongs to
+  fn device_type(&self) -> DeviceType;
+
+  /// Where the device's control API is reachable, for devices reached over the network
+  fn location(&self) -> Option<SocketAddr>;
+
+  /// Device information as of the last query
+  fn info(&self) -> &DeviceInfo;
+
+  /// What the device can do, as of the last query
+  fn capabilities(&self) -> Capabilities;
+
+  /// Delivers an input to the device, resolving once the device has acknowledged it.
+  fn send_input(&self, input: DeviceInput) -> BoxFuture<'_, crate::Result<()>>;
+
+  /// How long an input may take to be acknowledged before it is given up on. Platforms whose
+  /// next input may wait on someone accepting a pairing prompt allow that long.
+  fn input_timeout(&self) -> Duration { REQUEST_TIMEOUT }
+
+  /// Queries the device again, producing an up-to-date copy of it.
+  fn query_state(&self) -> BoxFuture<'_, crate::Result<Device>>;
+
+  /// Apps installed on the device, for platforms that can list them
+  fn apps(&self) -> BoxFuture<'_, anyhow::Result<Vec<App>>> {
+    Box::pin(async move { Err(anyhow!("{} devices cannot list their apps", self.device_type())) })
+  }
+
+  /// The foreground app and what its player is doing, for platforms that report it
+  fn app_state(&self) -> BoxFuture<'_, anyhow::Result<AppState>> {
+    Box::pin(async move { Err(anyhow!("{} devices do not report what they are playing", self.device_type())) })
+  }
+
+  /// An installed app's icon as an image file (PNG on Roku), for platforms that serve them
+  fn icon<'a>(&'a self, _app_id: &'a str) -> BoxFuture<'a, anyhow::Result<Vec<u8>>> {
+    Box::pin(async move { Err(anyhow!("{} devices do not serve app icons", self.device_type())) })
+  }
+
+  /// Captures the screen as an image file, for devices that allow it
+  fn screenshot(&self) -> BoxFuture<'_, anyhow::Result<Vec<u8>>> {
+    Box::pin(async move { Err(anyhow!("{} devices cannot capture their screen", self.device_type())) })
+  }
+
+  /// Streams the log output of apps under development, for devices with a debug console
+  fn debug_console(&self) -> BoxFuture<'_, anyhow::Result<UnboundedReceiver<LogLine>>> {
+    Box::pin(async move { Err(anyhow!("{} devices have no debug console", self.device_type())) })
+  }
+
+  /// Channels the TV's tuner has found, for TVs with one
+  fn channels(&self) -> BoxFuture<'_, anyhow::Result<Vec<Channel>>> {
+    Box::pin(async move { Err(anyhow!("{} devices have no tuner", self.device_type())) })
+  }
+
+  /// The channel currently tuned, if the tuner is the active input
+  fn active_channel(&self) -> BoxFuture<'_, anyhow::Result<Option<Channel>>> {
+    Box::pin(async move { Err(anyhow!("{} devices have no tuner", self.device_type())) })
+  }
+
+  /// The error for a key the platform has no way to deliver (Roku has every key)
+  fn unsupported_key(&self, key: Key) -> anyhow::Error where Self: Sized {
+    anyhow!("{} devices have no {:?} key", self.device_type(), key)
+  }
+
+  /// How to find the device, for display
+  fn address(&self) -> String {
+    self.location().map(|l| l.ip().to_string()).unwrap_or_default()
+  }
+
+  /// Identifies the device across queries and rediscovery. Serial numbers stay put even when a
+  /// device changes IP address; devices that report none are told apart by platform and address.
+  fn id(&self) -> String {
+    match self.info().product.serial_number.as_str() {
+      "" => format!("{}@{}", self.device_type(), self.address()),
+      serial => serial.to_owned()
+    }
+  }
+}
@@ -0,0 +1,20 @@
+mod roku_app;
+mod roku_app_state;
+mod roku_channel;
+mod roku_console;
+mod roku_developer;
+mod roku_device;
+mod roku_device_info;
+mod roku_discovery;
+mod roku_key;
+mod roku_input;
+
+pub use roku_app::*;
+pub use roku_app_state::*;
+pub use roku_channel::*;
+pub use roku_console::*;
+pub use roku_developer::*;
+pub use roku_device::*;
+pub use roku_device_info::*;
+pub use roku_key::*;
+pub use roku_input::*;
@@ -0,0 +1,56 @@
+use crate::devices::App;
+
+/// One entry of `/query/apps`, e.g. `<app id="12" type="appl" version="4.2.81179021">Netflix</app>`
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct RokuApp {
+  #[serde(rename="id")]
+  pub id: String,
+
+  /// `appl` for channels, `tvin` for a Roku TV's own inputs
+  #[serde(rename="type", default)]
+  pub app_type: String,
+
+  #[serde(rename="$value", default)]
+  pub name: String
+}
+
+/// The `/query/apps` document: every channel installed, in home screen order
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(rename="apps")]
+pub struct RokuApps {
+  #[serde(rename="app", default)]
+  pub apps: Vec<RokuApp>
+}
+
+impl RokuApps {
+  /// The installed channels, leaving out TV inputs, which have their own menu
+  pub fn channels(self) -> Vec<App> {
+    self.apps
+      .into_iter()
+      .filter(|app| app.app_type == "appl")
+      .map(|app| App { id: app.id, name: app.name })
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parses_app_list() {
+    let document = r#"<?xml version="1.0" encoding="UTF-8" ?>
+<apps>
+	<app id="tvinput.hdmi1" type="tvin" version="1.0.0">Game Console</app>
+	<app id="12" type="appl" version="5.1.91079006">Netflix</app>
+	<app id="837" type="appl" version="2.21.100">YouTube</app>
+</apps>"#;
+
+    let apps: RokuApps = serde_xml_rs::from_str(document).unwrap();
+
+    assert_eq!(apps.channels(), vec![
+      App { id: "12".into(), name: "Netflix".into() },
+      App { id: "837".into(), name: "YouTube".into() }
+    ]);
+  }
+}
@@ -0,0 +1,91 @@
+use crate::devices::{AppState, MediaState};
+
+/// The `/query/active-app` document, e.g. `<active-app><app id="12" type="appl" version="5.1.91">Netflix</app></active-app>`.
+/// On the home screen the app has no id and is just `<app>Roku</app>`.
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(rename="active-app")]
+pub struct RokuActiveApp {
+  pub app: RokuActiveAppEntry
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct RokuActiveAppEntry {
+  #[serde(default)]
+  pub id: Option<String>,
+
+  #[serde(rename="$value", default)]
+  pub name: String
+}
+
+/// The `/query/media-player` document, describing whatever the foreground app is playing
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(rename="player")]
+pub struct RokuMediaPlayer {
+  /// `none` when nothing is open, otherwise e.g. `open`, `buffer`, `play`, `pause`, `stop` or `close`
+  pub state: String,
+
+  /// e.g. `123456 ms`
+  #[serde(default)]
+  pub position: Option<String>,
+
+  #[serde(default)]
+  pub duration: Option<String>
+}
+
+impl RokuActiveApp {
+  /// Combines the foreground app with what its player is doing.
+  pub fn with_player(self, player: RokuMediaPlayer) -> AppState {
+    let media = match player.state.as_str() {
+      "none" | "close" => None,
+      _ => Some(MediaState {
+        position_ms: player.position.as_deref().and_then(milliseconds),
+        duration_ms: player.duration.as_deref().and_then(milliseconds),
+        state: player.state
+      })
+    };
+
+    AppState { app_id: self.app.id, app_name: self.app.name, media }
+  }
+}
+
+/// Reads a duration the way ECP writes them, e.g. `123456 ms`
+fn milliseconds(value: &str) -> Option<u64> {
+  value.trim().strip_suffix("ms")?.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn reads_what_is_playing() {
+    let active: RokuActiveApp = serde_xml_rs::from_str(r#"<?xml version="1.0" encoding="UTF-8" ?>
+<active-app>
+	<app id="12" type="appl" version="5.1.91079006">Netflix</app>
+</active-app>"#).unwrap();
+
+    let player: RokuMediaPlayer = serde_xml_rs::from_str(r#"<?xml version="1.0" encoding="UTF-8" ?>
+<player error="false" state="play">
+	<plugin bandwidth="16219136 bps" id="12" name="Netflix"/>
+	<format audio="eac3" captions="none" drm="widevine" video="hevc_b"/>
+	<buffering current="1000" max="1000" target="0"/>
+	<position>81925 ms</position>
+	<duration>3012000 ms</duration>
+	<is_live>false</is_live>
+</player>"#).unwrap();
+
+    assert_eq!(active.with_player(player), AppState {
+      app_id: Some("12".into()),
+      app_name: "Netflix".into(),
+      media: Some(MediaState { state: "play".into(), position_ms: Some(81925), duration_ms: Some(3012000) })
+    });
+  }
+
+  #[test]
+  fn reads_the_home_screen() {
+    let active: RokuActiveApp = serde_xml_rs::from_str("<active-app><app>Roku</app></active-app>").unwrap();
+    let player: RokuMediaPlayer = serde_xml_rs::from_str(r#"<player error="false" state="none"/>"#).unwrap();
+
+    assert_eq!(active.with_player(player), AppState { app_id: None, app_name: "Roku".into(), media: None });
+  }
+}
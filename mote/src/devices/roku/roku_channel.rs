@@ -0,0 +1,102 @@
+use crate::devices::Channel;
+
+/// One entry of `/query/tv-channels` or `/query/tv-active-channel`
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct RokuChannel {
+  #[serde(rename="number")]
+  pub number: String,
+
+  #[serde(rename="name", default)]
+  pub name: String,
+
+  /// Channels the user hid from the guide
+  #[serde(rename="user-hidden", default)]
+  pub user_hidden: bool
+}
+
+/// The `/query/tv-channels` document: every channel the tuner has found
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(rename="tv-channels")]
+pub struct RokuChannels {
+  #[serde(rename="channel", default)]
+  pub channels: Vec<RokuChannel>
+}
+
+/// The `/query/tv-active-channel` document. There is no channel unless the tuner is the active input.
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(rename="tv-channel")]
+pub struct RokuActiveChannel {
+  #[serde(rename="channel")]
+  pub channel: Option<RokuChannel>
+}
+
+impl From<RokuChannel> for Channel {
+  fn from(channel: RokuChannel) -> Channel {
+    Channel { number: channel.number, name: channel.name }
+  }
+}
+
+impl RokuChannels {
+  /// The channels the user has not hidden
+  pub fn visible(self) -> Vec<Channel> {
+    self.channels.into_iter().filter(|c| !c.user_hidden).map(Channel::from).collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parses_channel_list() {
+    let document = r#"<?xml version="1.0" encoding="UTF-8" ?>
+<tv-channels>
+	<channel>
+		<number>2.1</number>
+		<name>KTVU-HD</name>
+		<type>air-digital</type>
+		<user-hidden>false</user-hidden>
+	</channel>
+	<channel>
+		<number>2.2</number>
+		<name>Movies!</name>
+		<type>air-digital</type>
+		<user-hidden>true</user-hidden>
+	</channel>
+	<channel>
+		<number>4.1</number>
+		<name>KRON-HD</name>
+		<type>air-digital</type>
+		<user-hidden>false</user-hidden>
+	</channel>
+</tv-channels>"#;
+
+    let channels: RokuChannels = serde_xml_rs::from_str(document).unwrap();
+
+    assert_eq!(channels.visible(), vec![
+      Channel { number: "2.1".into(), name: "KTVU-HD".into() },
+      Channel { number: "4.1".into(), name: "KRON-HD".into() }
+    ]);
+  }
+
+  #[test]
+  fn parses_active_channel() {
+    let tuned = r#"<tv-channel>
+	<channel>
+		<number>4.1</number>
+		<name>KRON-HD</name>
+		<type>air-digital</type>
+		<user-hidden>false</user-hidden>
+		<active-input>true</active-input>
+		<signal-state>valid</signal-state>
+		<program-title>Evening News</program-title>
+	</channel>
+</tv-channel>"#;
+
+    let active: RokuActiveChannel = serde_xml_rs::from_str(tuned).unwrap();
+    assert_eq!(active.channel.unwrap().number, "4.1");
+
+    let elsewhere: RokuActiveChannel = serde_xml_rs::from_str("<tv-channel></tv-channel>").unwrap();
+    assert!(elsewhere.channel.is_none());
+  }
+}
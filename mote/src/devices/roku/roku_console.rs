@@ -0,0 +1,127 @@
+use std::{net::IpAddr, time::Duration};
+use anyhow::{anyhow, Context};
+use tokio::{io::AsyncReadExt, net::TcpStream, select, spawn, sync::mpsc::{unbounded_channel, UnboundedReceiver}, time::timeout};
+use crate::devices::LogLine;
+
+/// Port the BrightScript debug console listens on, once developer mode is enabled
+const DEBUG_CONSOLE_PORT: u16 = 8085;
+
+/// How long to wait for the console to accept the connection
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Connects to the BrightScript debug console, where sideloaded channels' output and errors
+/// are printed, streaming its lines until the device hangs up or the receiver is dropped.
+pub async fn connect_debug_console(host: IpAddr) -> anyhow::Result<UnboundedReceiver<LogLine>> {
+  let mut stream = timeout(CONNECT_TIMEOUT, TcpStream::connect((host, DEBUG_CONSOLE_PORT)))
+    .await
+    .map_err(|_| anyhow!("Timed out connecting to the debug console on port {}", DEBUG_CONSOLE_PORT))?
+    .with_context(|| format!("Failed to connect to the debug console on port {}, is developer mode enabled?", DEBUG_CONSOLE_PORT))?;
+
+  let (tx, rx) = unbounded_channel();
+
+  spawn(async move {
+    let mut decoder = ConsoleDecoder::default();
+    let mut buffer = [0; 4096];
+
+    loop {
+      let read = select! {
+        _ = tx.closed() => break,
+        read = stream.read(&mut buffer) => read
+      };
+
+      let Ok(count @ 1..) = read else { break };
+      for line in decoder.feed(&buffer[..count]) {
+        if tx.send(LogLine::now(line)).is_err() { return }
+      }
+    }
+  });
+
+  Ok(rx)
+}
+
+/// Where in a telnet command the decoder is
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum Telnet {
+  #[default]
+  Data,
+
+  /// After IAC
+  Command,
+
+  /// After IAC WILL/WONT/DO/DONT, awaiting the option
+  Option,
+
+  /// Inside IAC SB ... IAC SE
+  Subnegotiation,
+
+  /// After an IAC inside a subnegotiation
+  SubnegotiationCommand
+}
+
+/// Splits the console's byte stream into lines. The console speaks telnet, so negotiation
+/// is dropped, as are carriage returns.
+#[derive(Debug, Default)]
+struct ConsoleDecoder {
+  line: Vec<u8>,
+  telnet: Telnet
+}
+
+impl ConsoleDecoder {
+  const IAC: u8 = 255;
+  const SB: u8 = 250;
+  const SE: u8 = 240;
+
+  /// Consumes bytes as they arrive, returning the lines they complete.
+  fn feed(&mut self, bytes: &[u8]) -> Vec<String> {
+    let mut lines = vec![];
+
+    for &byte in bytes {
+      self.telnet = match (self.telnet, byte) {
+        (Telnet::Data, Self::IAC) => Telnet::Command,
+        (Telnet::Data, b'\n') => {
+          lines.push(String::from_utf8_lossy(&self.line).into_owned());
+          self.line.clear();
+          Telnet::Data
+        },
+        (Telnet::Data, b'\r') => Telnet::Data,
+        (Telnet::Data, _) => { self.line.push(byte); Telnet::Data },
+
+        // an escaped 255 is data
+        (Telnet::Command, Self::IAC) => { self.line.push(byte); Telnet::Data },
+        (Telnet::Command, 251..=254) => Telnet::Option,
+        (Telnet::Command, Self::SB) => Telnet::Subnegotiation,
+        (Telnet::Command, _) | (Telnet::Option, _) => Telnet::Data,
+
+        (Telnet::Subnegotiation, Self::IAC) => Telnet::SubnegotiationCommand,
+        (Telnet::Subnegotiation, _) => Telnet::Subnegotiation,
+        (Telnet::SubnegotiationCommand, Self::SE) => Telnet::Data,
+        (Telnet::SubnegotiationCommand, _) => Telnet::Subnegotiation
+      };
+    }
+
+    lines
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn splits_lines_across_reads() {
+    let mut decoder = ConsoleDecoder::default();
+
+    assert_eq!(decoder.feed(b"------ Compiling dev 'Hello' ------\r\n[scrpt.cmpl] Compil"), vec!["------ Compiling dev 'Hello' ------"]);
+    assert_eq!(decoder.feed(b"ing 'Hello', id 'dev'\r\n"), vec!["[scrpt.cmpl] Compiling 'Hello', id 'dev'"]);
+    assert!(decoder.feed(b"Brightscript Debugger> ").is_empty());
+  }
+
+  #[test]
+  fn drops_telnet_negotiation() {
+    let mut decoder = ConsoleDecoder::default();
+
+    // IAC WILL ECHO, IAC SB NAWS ... IAC SE, then an escaped 255 in the text
+    let bytes = [&[255, 251, 1][..], &[255, 250, 31, 0, 80, 0, 24, 255, 240], b"ok", &[255, 255], b"\r\n"].concat();
+    assert_eq!(decoder.feed(&bytes), vec!["ok\u{fffd}"]);
+  }
+}
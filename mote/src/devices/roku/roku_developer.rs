@@ -0,0 +1,263 @@
+use std::{net::IpAddr, time::{Duration, SystemTime, UNIX_EPOCH}};
+use anyhow::{anyhow, bail};
+use digest_auth::{AuthContext, HttpMethod};
+use reqwest::{header::{AUTHORIZATION, WWW_AUTHENTICATE}, multipart::{Form, Part}, Method, RequestBuilder, Response, StatusCode};
+use crate::{config::{Config, DeveloperCredentials}, devices::discovery::CLIENT};
+
+/// Port the developer web installer listens on
+const INSTALLER_PORT: u16 = 80;
+
+/// How long an installer request may take; installing and packaging compile the channel on the device
+const INSTALLER_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// The web installer a Roku serves once developer mode is enabled, where channels are
+/// sideloaded and screenshots taken. Every request is digest-authenticated.
+#[derive(Debug, Clone)]
+pub struct RokuDeveloper {
+  base_url: String,
+  username: String,
+  password: String
+}
+
+impl RokuDeveloper {
+  pub fn new(host: IpAddr, credentials: &DeveloperCredentials) -> anyhow::Result<Self> {
+    let Some(password) = credentials.password.clone() else {
+      bail!("No developer password configured, set `password` under [developer] in {}", Config::path().display())
+    };
+
+    Ok(RokuDeveloper { base_url: format!("http://{}:{}", host, INSTALLER_PORT), username: credentials.username.clone(), password })
+  }
+
+  /// Sends a request, answering the installer's digest challenge. `build` is called once
+  /// per attempt, since multipart bodies cannot be replayed. Error statuses other than a
+  /// rejected password are left to the caller, as the installer explains them in the body.
+  async fn send(&self, method: Method, path: &str, build: impl Fn(RequestBuilder) -> RequestBuilder) -> anyhow::Result<Response> {
+    let url = format!("{}{}", self.base_url, path);
+
+    let response = build(CLIENT.request(method.clone(), &url).timeout(INSTALLER_TIMEOUT)).send().await?;
+    if response.status() != StatusCode::UNAUTHORIZED { return Ok(response) }
+
+    let challenge = response
+      .headers()
+      .get(WWW_AUTHENTICATE)
+      .ok_or_else(|| anyhow!("Developer installer refused the request without a digest challenge"))?
+      .to_str()?;
+
+    let context = AuthContext::new_with_method(&self.username, &self.password, path, None::<&[u8]>, HttpMethod::from(method.as_str()));
+    let authorization = digest_auth::parse(challenge)?.respond(&context)?.to_header_string();
+
+    let response = build(CLIENT.request(method, &url).timeout(INSTALLER_TIMEOUT)).header(AUTHORIZATION, authorization).send().await?;
+    match response.status() {
+      StatusCode::UNAUTHORIZED => bail!("Developer password was rejected, check [developer] in {}", Config::path().display()),
+      _ => Ok(response)
+    }
+  }
+
+  /// Posts a form to one of the installer's pages, returning the messages its result page shows.
+  async fn submit(&self, path: &str, form: impl Fn() -> Form) -> anyhow::Result<Vec<String>> {
+    let response = self.send(Method::POST, path, |request| request.multipart(form())).await?;
+    let status = response.status();
+    let messages = messages(&response.text().await?);
+
+    match status.is_success() {
+      true => Ok(messages),
+      false if messages.is_empty() => bail!("Developer installer answered {}", status),
+      false => bail!("{}", messages.join("\n"))
+    }
+  }
+
+  /// Downloads a file the installer produced, e.g. `pkgs/dev.jpg`.
+  async fn download(&self, path: &str) -> anyhow::Result<Vec<u8>> {
+    Ok(self.send(Method::GET, &format!("/{}", path), |request| request).await?.error_for_status()?.bytes().await?.to_vec())
+  }
+
+  /// Sideloads a zipped channel, replacing whichever one was sideloaded before.
+  /// Returns the installer's messages, or fails with them (and any compile errors).
+  pub async fn install(&self, archive: Vec<u8>) -> anyhow::Result<Vec<String>> {
+    let messages = self.submit("/plugin_install", || Form::new()
+      .text("mysubmit", "Install")
+      .part("archive", Part::bytes(archive.clone()).file_name("channel.zip").mime_str("application/zip").expect("Valid MIME type"))
+    ).await?;
+
+    match messages.iter().any(|message| message.contains("Failure")) {
+      true => bail!("{}", messages.join("\n")),
+      false => Ok(messages)
+    }
+  }
+
+  /// Removes the sideloaded channel.
+  pub async fn delete(&self) -> anyhow::Result<Vec<String>> {
+    self.submit("/plugin_install", || Form::new().text("mysubmit", "Delete").text("archive", "")).await
+  }
+
+  /// Signs the sideloaded channel into a `.pkg` for the channel store, with the signing key
+  /// the device was keyed with (see `genkey`). `name` is the `name/version` Roku records.
+  pub async fn package(&self, name: &str, signing_password: &str) -> anyhow::Result<Vec<u8>> {
+    let page = self
+      .send(Method::POST, "/plugin_package", |request| request.multipart(Form::new()
+        .text("mysubmit", "Package")
+        .text("app_name", name.to_owned())
+        .text("passwd", signing_password.to_owned())
+        .text("pkg_time", SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis().to_string())
+      ))
+      .await?
+      .text()
+      .await?;
+
+    match package_path(&page) {
+      Some(path) => self.download(&path).await,
+      None => bail!("{}", Some(messages(&page).join("\n")).filter(|m| !m.is_empty()).unwrap_or_else(|| "No package was produced".to_owned()))
+    }
+  }
+
+  /// Captures the screen, returning the image file the device wrote (JPEG on most models).
+  /// Roku only allows this while a sideloaded channel is running.
+  pub async fn screenshot(&self) -> anyhow::Result<Vec<u8>> {
+    let page = self
+      .send(Method::POST, "/plugin_inspect", |request| request.multipart(
+        Form::new().text("mysubmit", "Screenshot").text("archive", "").text("passwd", "")
+      ))
+      .await?
+      .error_for_status()?
+      .text()
+      .await?;
+
+    let path = screenshot_path(&page).ok_or_else(|| anyhow!("No screenshot was taken, is a sideloaded channel running?"))?;
+    self.download(path).await
+  }
+}
+
+/// Where the installer's result page says the screenshot went, e.g. `pkgs/dev.jpg?time=1700000000`
+fn screenshot_path(page: &str) -> Option<&str> {
+  let start = page.find("pkgs/dev.")?;
+  let rest = &page[start..];
+  let end = rest.find(|c: char| c == '"' || c == '\'' || c == '>' || c.is_whitespace()).unwrap_or(rest.len());
+
+  Some(&rest[..end])
+}
+
+/// Where the packager's result page links the signed package, e.g. `pkgs/P1a2b3c.pkg`
+fn package_path(page: &str) -> Option<String> {
+  let end = page.find(".pkg")? + ".pkg".len();
+  let start = page[..end].rfind("pkgs/")?;
+
+  // the page links it as `pkgs//P1a2b3c.pkg`
+  Some(page[start..end].replace("//", "/"))
+}
+
+/// The messages an installer page shows: the current firmware sets them from script, older
+/// firmware prints them (compile errors included) in red.
+fn messages(page: &str) -> Vec<String> {
+  let scripted = page
+    .split("'Set message content', '")
+    .skip(1)
+    .filter_map(|rest| rest.split_once("')").map(|(message, _)| message));
+
+  let printed = page
+    .split("<font color=\"red\">")
+    .skip(1)
+    .filter_map(|rest| rest.split_once("</font>").map(|(message, _)| message));
+
+  scripted
+    .chain(printed)
+    .flat_map(|message| message.split("<br>"))
+    .map(|line| line.trim().to_owned())
+    .filter(|line| !line.is_empty())
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use tokio::{io::{AsyncReadExt, AsyncWriteExt}, net::TcpListener, task::JoinHandle};
+  use super::*;
+
+  /// Serves `responses` in order, one connection each, returning the requests it saw.
+  async fn stand_in(responses: Vec<String>) -> (String, JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+    let installer = tokio::spawn(async move {
+      let mut requests = vec![];
+
+      for response in responses {
+        let (mut stream, _) = listener.accept().await.unwrap();
+
+        let mut request = vec![];
+        let mut buffer = [0; 4096];
+        loop {
+          let read = stream.read(&mut buffer).await.unwrap();
+          request.extend_from_slice(&buffer[..read]);
+
+          let text = String::from_utf8_lossy(&request);
+          let Some((headers, body)) = text.split_once("\r\n\r\n") else { continue };
+          let length = headers
+            .lines()
+            .find_map(|line| line.to_ascii_lowercase().strip_prefix("content-length:").map(|l| l.trim().parse::<usize>().unwrap()))
+            .unwrap_or(0);
+
+          if body.len() >= length || read == 0 { break }
+        }
+
+        stream.write_all(response.as_bytes()).await.unwrap();
+        requests.push(String::from_utf8_lossy(&request).into_owned());
+      }
+
+      requests
+    });
+
+    (base_url, installer)
+  }
+
+  fn response(status: &str, headers: &str, body: &str) -> String {
+    format!("HTTP/1.1 {}\r\nConnection: close\r\n{}Content-Length: {}\r\n\r\n{}", status, headers, body.len(), body)
+  }
+
+  #[tokio::test]
+  async fn answers_digest_challenges() {
+    let challenge = "WWW-Authenticate: Digest realm=\"rokudev\", nonce=\"1700000000\", qop=\"auth\"\r\n";
+    let page = r#"<img src="pkgs/dev.jpg?time=1700000000">"#;
+
+    let (base_url, installer) = stand_in(vec![
+      response("401 Unauthorized", challenge, ""),
+      response("200 OK", "", page),
+      response("401 Unauthorized", challenge, ""),
+      response("200 OK", "Content-Type: image/jpeg\r\n", "jpeg")
+    ]).await;
+
+    let developer = RokuDeveloper { base_url, username: "rokudev".into(), password: "1234".into() };
+    assert_eq!(developer.screenshot().await.unwrap(), b"jpeg");
+
+    let requests = installer.await.unwrap();
+    assert!(requests[0].starts_with("POST /plugin_inspect "));
+    assert!(!requests[0].contains("authorization:"));
+    assert!(requests[1].contains(r#"authorization: Digest username="rokudev", realm="rokudev", nonce="1700000000", uri="/plugin_inspect""#));
+    assert!(requests[1].contains("Screenshot"));
+    assert!(requests[3].starts_with("GET /pkgs/dev.jpg?time=1700000000 "));
+  }
+
+  #[tokio::test]
+  async fn reports_install_failures() {
+    let page = "<script>Shell.create('Roku.Message').trigger('Set message type', 'error').trigger('Set message content', 'Install Failure: Compilation Failed.').trigger('Render', node);</script>";
+    let (base_url, _) = stand_in(vec![response("200 OK", "", page)]).await;
+
+    let developer = RokuDeveloper { base_url, username: "rokudev".into(), password: "1234".into() };
+    let error = developer.install(b"PK".to_vec()).await.unwrap_err();
+
+    assert_eq!(error.to_string(), "Install Failure: Compilation Failed.");
+  }
+
+  #[test]
+  fn reads_messages() {
+    let page = r#"<font color="red">Install Failure: Compilation Failed<br>pkg:/source/main.brs(12): Syntax Error</font>
+      <script>Shell.create('Roku.Message').trigger('Set message content', 'Received 5120 bytes.').trigger('Render', node);</script>"#;
+
+    assert_eq!(messages(page), vec!["Received 5120 bytes.", "Install Failure: Compilation Failed", "pkg:/source/main.brs(12): Syntax Error"]);
+    assert_eq!(package_path(r#"<a href="pkgs//P4f2a9c.pkg">P4f2a9c.pkg</a>"#), Some("pkgs/P4f2a9c.pkg".to_owned()));
+  }
+
+  #[test]
+  fn finds_screenshot_path() {
+    assert_eq!(screenshot_path(r#"Shell.create('Roku.Message').trigger('Set message content', '<img src="pkgs/dev.png?time=12">')"#), Some("pkgs/dev.png?time=12"));
+    assert_eq!(screenshot_path("Screenshot failed"), None);
+  }
+}
@@ -0,0 +1,214 @@
+use std::{net::SocketAddr, sync::Arc};
+use futures::future::BoxFuture;
+use tokio::sync::mpsc::UnboundedReceiver;
+use crate::{config::Config, devices::{
+  App, AppState, Capabilities, Channel, Device, DeviceInfo, DeviceInput, DeviceType, FormFactor, LogLine, Model, Network, NetworkType, PowerMode, Product,
+  RemoteDevice, System, Uptime,
+  discovery::CLIENT
+}};
+use super::{connect_debug_console, roku_discovery, RokuActiveApp, RokuActiveChannel, RokuApps, RokuChannels, RokuDeveloper, RokuDeviceInfo, RokuInput, RokuMediaPlayer, RokuPowerMode};
+
+/// A Roku device, controlled over ECP (External Control Protocol) on port 8060.
+#[derive(Debug, Clone)]
+pub struct RokuDevice {
+  location: SocketAddr,
+  info: DeviceInfo,
+  capabilities: Capabilities
+}
+
+impl RokuDevice {
+  /// Gets detailed device info over HTTP.
+  pub async fn query(location: SocketAddr) -> crate::Result<Self> {
+    let response = 
+      CLIENT
+        .get(format!("http://{}:8060/query/device-info", location.ip()))
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+
+    let roku_info = RokuDeviceInfo::parse(&response)?;
+    let network_type = NetworkType::from(roku_info.network_type.as_str());
+    let capabilities = roku_info.capabilities();
+    let limited_control = roku_info.ecp_setting_mode.is_some().then(|| roku_info.is_limited());
+
+    let features = vec![
+      ("Find remote", roku_info.supports_find_remote),
+      ("5 GHz WiFi", roku_info.has_wifi_5g_support),
+      ("Private listening", roku_info.supports_private_listening),
+      ("Voice search", roku_info.voice_search_enabled),
+      ("AirPlay", roku_info.supports_airplay),
+      ("Wake on WLAN", roku_info.supports_wake_on_wlan)
+    ];
+
+    let RokuDeviceInfo {
+      name,
+      alternate_name,
+      model_name,
+      model_number: number,
+      vendor_name: vendor,
+      serial_number,
+      is_tv,
+      network_name,
+      wifi_mac_address,
+      ethernet_mac_address,
+      software_version,
+      software_build,
+      ui_resolution,
+      time_zone_name,
+      uptime_seconds,
+      power_mode,
+      developer_enabled,
+      ..
+    } = roku_info;
+
+    let info = DeviceInfo {
+      name,
+      product: Product {
+        vendor,
+        serial_number,
+        model: Model {
+          number,
+          alternate_name,
+          name: model_name,
+        },
+        form_factor: if is_tv { FormFactor::Tv } else { FormFactor::Streamer }
+      },
+      network: Network {
+        mac_address: match network_type {
+          NetworkType::WiFi     => wifi_mac_address,
+          NetworkType::Ethernet => ethernet_mac_address,
+          _ => wifi_mac_address.or(ethernet_mac_address)
+        }.unwrap_or_default(),
+        network_type,
+        network_name: network_name.unwrap_or_default()
+      },
+      system: System {
+        uptime: Some(Uptime::new(uptime_seconds)),
+        software_version: software_version.map(|version| match software_build {
+          Some(build) => format!("{} build {}", version, build),
+          None => version
+        }),
+        power_mode: power_mode.and_then(|mode| match mode {
+          RokuPowerMode::PowerOn => Some(PowerMode::On),
+          RokuPowerMode::DisplayOff => Some(PowerMode::DisplayOff),
+          RokuPowerMode::Headless | RokuPowerMode::Ready => Some(PowerMode::Standby),
+          RokuPowerMode::Unknown => None
+        }),
+        resolution: ui_resolution,
+        time_zone: time_zone_name,
+        developer_mode: Some(developer_enabled),
+        limited_control
+      },
+      now_playing: None,
+      features
+    };
+
+    // re-use location, just configure port to be correct for Roku devices
+    let mut location = location; location.set_port(8060);
+
+    Ok(RokuDevice { location, info, capabilities })
+  }
+
+  /// Fetches an ECP query document, e.g. `query/tv-channels`.
+  async fn query_document(&self, path: &str) -> crate::Result<String> {
+    Ok(
+      CLIENT
+        .get(format!("http://{}/{}", self.location, path))
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?
+    )
+  }
+}
+
+impl RemoteDevice for RokuDevice {
+  fn discover() -> UnboundedReceiver<Device> { roku_discovery::discover() }
+
+  fn device_type(&self) -> DeviceType { DeviceType::Roku }
+
+  fn location(&self) -> Option<SocketAddr> { Some(self.location) }
+
+  fn info(&self) -> &DeviceInfo { &self.info }
+
+  fn capabilities(&self) -> Capabilities { self.capabilities.clone() }
+
+  fn send_input(&self, input: DeviceInput) -> BoxFuture<'_, crate::Result<()>> {
+    Box::pin(async move {
+      for input in RokuInput::from_device_input(input) {
+        CLIENT
+          .post(format!("http://{}/{}", self.location, String::from(input)))
+          .send()
+          .await?
+          .error_for_status()?;
+      }
+
+      Ok(())
+    })
+  }
+
+  fn query_state(&self) -> BoxFuture<'_, crate::Result<Device>> {
+    Box::pin(async move {
+      Ok(Arc::new(RokuDevice::query(self.location).await?) as Device)
+    })
+  }
+
+  fn apps(&self) -> BoxFuture<'_, anyhow::Result<Vec<App>>> {
+    Box::pin(async move {
+      let apps: RokuApps = serde_xml_rs::from_str(&self.query_document("query/apps").await?)?;
+      Ok(apps.channels())
+    })
+  }
+
+  fn app_state(&self) -> BoxFuture<'_, anyhow::Result<AppState>> {
+    Box::pin(async move {
+      let (active, player) = futures::try_join!(self.query_document("query/active-app"), self.query_document("query/media-player"))?;
+
+      let active: RokuActiveApp = serde_xml_rs::from_str(&active)?;
+      let player: RokuMediaPlayer = serde_xml_rs::from_str(&player)?;
+      Ok(active.with_player(player))
+    })
+  }
+
+  fn icon<'a>(&'a self, app_id: &'a str) -> BoxFuture<'a, anyhow::Result<Vec<u8>>> {
+    Box::pin(async move {
+      Ok(
+        CLIENT
+          .get(format!("http://{}/query/icon/{}", self.location, app_id))
+          .send()
+          .await?
+          .error_for_status()?
+          .bytes()
+          .await?
+          .to_vec()
+      )
+    })
+  }
+
+  fn screenshot(&self) -> BoxFuture<'_, anyhow::Result<Vec<u8>>> {
+    Box::pin(async move {
+      RokuDeveloper::new(self.location.ip(), &Config::load()?.developer)?.screenshot().await
+    })
+  }
+
+  fn debug_console(&self) -> BoxFuture<'_, anyhow::Result<UnboundedReceiver<LogLine>>> {
+    Box::pin(connect_debug_console(self.location.ip()))
+  }
+
+  fn channels(&self) -> BoxFuture<'_, anyhow::Result<Vec<Channel>>> {
+    Box::pin(async move {
+      let channels: RokuChannels = serde_xml_rs::from_str(&self.query_document("query/tv-channels").await?)?;
+      Ok(channels.visible())
+    })
+  }
+
+  fn active_channel(&self) -> BoxFuture<'_, anyhow::Result<Option<Channel>>> {
+    Box::pin(async move {
+      let active: RokuActiveChannel = serde_xml_rs::from_str(&self.query_document("query/tv-active-channel").await?)?;
+      Ok(active.channel.map(Channel::from))
+    })
+  }
+}
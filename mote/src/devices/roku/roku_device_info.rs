@@ -0,0 +1,341 @@
+use std::collections::HashMap;
+use crate::devices::{Capabilities, Capability};
+
+/// Power state as reported in `power-mode`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Deserialize)]
+pub enum RokuPowerMode {
+  PowerOn,
+
+  /// Screen off, e.g. while casting audio to a Roku TV
+  DisplayOff,
+
+  /// Roku TVs in fast-start standby
+  Headless,
+
+  Ready,
+
+  #[serde(other)]
+  Unknown
+}
+
+/// Roku-specific representation of device info: the complete `/query/device-info` document.
+/// Firmware adds fields over time, so anything not modelled here is kept in `other`.
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(rename="device-info")]
+#[allow(dead_code)] // the whole document is modelled, not every field is shown
+pub struct RokuDeviceInfo {
+  /// Human-readable device name
+  #[serde(rename="friendly-device-name")]
+  pub name: String,
+
+  /// Name set by the user, if any
+  #[serde(rename="user-device-name")]
+  pub user_device_name: Option<String>,
+
+  /// Room the user placed the device in, if any
+  #[serde(rename="user-device-location")]
+  pub user_device_location: Option<String>,
+
+  #[serde(rename="udn")]
+  pub udn: Option<String>,
+
+  /// Human-readable vendor name
+  #[serde(rename="vendor-name")]
+  pub vendor_name: String,
+
+  /// Human-readable model brand name
+  #[serde(rename="friendly-model-name")]
+  pub model_name: String,
+
+  /// Internal model name
+  #[serde(rename="model-name")]
+  pub alternate_name: String,
+
+  #[serde(rename="model-number")]
+  pub model_number: String,
+
+  #[serde(rename="model-region")]
+  pub model_region: Option<String>,
+
+  #[serde(rename="serial-number")]
+  pub serial_number: String,
+
+  #[serde(rename="device-id")]
+  pub device_id: Option<String>,
+
+  /// Roku TV rather than a streaming player
+  #[serde(rename="is-tv", default)]
+  pub is_tv: bool,
+
+  #[serde(rename="is-stick", default)]
+  pub is_stick: bool,
+
+  /// Diagonal in inches, on Roku TVs
+  #[serde(rename="screen-size")]
+  pub screen_size: Option<u32>,
+
+  /// e.g. `ATSC` or `DVB-T`, on Roku TVs with a tuner
+  #[serde(rename="tuner-type")]
+  pub tuner_type: Option<String>,
+
+  /// Resolution the UI renders at, e.g. `1080p`
+  #[serde(rename="ui-resolution")]
+  pub ui_resolution: Option<String>,
+
+  #[serde(rename="network-type")]
+  pub network_type: String,
+
+  #[serde(rename="network-name")]
+  pub network_name: Option<String>,
+
+  #[serde(rename="supports-ethernet", default)]
+  pub supports_ethernet: bool,
+
+  /// Wireless MAC address (if this device uses WiFi)
+  #[serde(rename="wifi-mac")]
+  pub wifi_mac_address: Option<String>,
+
+  /// Etherent MAC address (if this device uses Ethernet)
+  #[serde(rename="ethernet-mac")]
+  pub ethernet_mac_address: Option<String>,
+
+  #[serde(rename="has-wifi-5G-support", default)]
+  pub has_wifi_5g_support: bool,
+
+  #[serde(rename="software-version")]
+  pub software_version: Option<String>,
+
+  #[serde(rename="software-build")]
+  pub software_build: Option<String>,
+
+  #[serde(rename="language")]
+  pub language: Option<String>,
+
+  #[serde(rename="country")]
+  pub country: Option<String>,
+
+  #[serde(rename="locale")]
+  pub locale: Option<String>,
+
+  /// Whether the time zone follows the network
+  #[serde(rename="time-zone-auto", default)]
+  pub time_zone_auto: bool,
+
+  /// Roku's own time zone id, e.g. `US/Pacific`
+  #[serde(rename="time-zone")]
+  pub time_zone: Option<String>,
+
+  /// Human-readable time zone, e.g. `United States/Pacific`
+  #[serde(rename="time-zone-name")]
+  pub time_zone_name: Option<String>,
+
+  /// IANA time zone, e.g. `America/Los_Angeles`
+  #[serde(rename="time-zone-tz")]
+  pub time_zone_tz: Option<String>,
+
+  /// Offset from UTC in minutes
+  #[serde(rename="time-zone-offset")]
+  pub time_zone_offset: Option<i32>,
+
+  #[serde(rename="clock-format")]
+  pub clock_format: Option<String>,
+
+  #[serde(rename="uptime")]
+  pub uptime_seconds: u64,
+
+  #[serde(rename="power-mode")]
+  pub power_mode: Option<RokuPowerMode>,
+
+  #[serde(rename="supports-suspend", default)]
+  pub supports_suspend: bool,
+
+  /// Whether the remote has a speaker the device can ring
+  #[serde(rename="supports-find-remote", default)]
+  pub supports_find_remote: bool,
+
+  #[serde(rename="supports-private-listening", default)]
+  pub supports_private_listening: bool,
+
+  #[serde(rename="headphones-connected", default)]
+  pub headphones_connected: bool,
+
+  #[serde(rename="supports-audio-guide", default)]
+  pub supports_audio_guide: bool,
+
+  #[serde(rename="voice-search-enabled", default)]
+  pub voice_search_enabled: bool,
+
+  #[serde(rename="supports-airplay", default)]
+  pub supports_airplay: bool,
+
+  #[serde(rename="supports-wake-on-wlan", default)]
+  pub supports_wake_on_wlan: bool,
+
+  /// Whether sideloading a developer channel is enabled
+  #[serde(rename="developer-enabled", default)]
+  pub developer_enabled: bool,
+
+  /// How much of ECP the device allows from the network (*Control by mobile apps*), e.g. `limited`.
+  /// Older software does not report it, and allows everything.
+  #[serde(rename="ecp-setting-mode")]
+  pub ecp_setting_mode: Option<String>,
+
+  /// Every field not modelled above, verbatim
+  #[serde(skip)]
+  pub other: HashMap<String, String>
+}
+
+/// Every field `RokuDeviceInfo` models, so the rest can be told apart
+const MODELLED_FIELDS: [&str; 45] = [
+  "friendly-device-name", "user-device-name", "user-device-location", "udn", "vendor-name", "friendly-model-name",
+  "model-name", "model-number", "model-region", "serial-number", "device-id", "is-tv", "is-stick", "screen-size",
+  "tuner-type", "ui-resolution", "network-type", "network-name", "supports-ethernet", "wifi-mac", "ethernet-mac",
+  "has-wifi-5G-support", "software-version", "software-build", "language", "country", "locale", "time-zone-name",
+  "time-zone-tz", "time-zone-offset", "time-zone", "time-zone-auto", "clock-format", "uptime", "power-mode",
+  "supports-suspend", "supports-find-remote", "supports-private-listening", "headphones-connected",
+  "supports-audio-guide", "voice-search-enabled", "supports-airplay", "supports-wake-on-wlan", "developer-enabled",
+  "ecp-setting-mode"
+];
+
+impl RokuDeviceInfo {
+  /// Parses a `/query/device-info` document, keeping unmodelled fields in `other`.
+  pub fn parse(document: &str) -> crate::Result<Self> {
+    let mut info: RokuDeviceInfo = serde_xml_rs::from_str(document)?;
+
+    let mut fields: HashMap<String, String> = serde_xml_rs::from_str(document)?;
+    fields.retain(|name, _| !MODELLED_FIELDS.contains(&name.as_str()));
+    info.other = fields;
+
+    Ok(info)
+  }
+
+  /// Whether ECP is in Limited mode, which refuses keypresses and most queries from the network
+  pub fn is_limited(&self) -> bool {
+    self.ecp_setting_mode.as_deref().is_some_and(|mode| mode.eq_ignore_ascii_case("limited"))
+  }
+
+  /// What the device can do. Every Roku lists and launches channels and takes volume keys, which streaming players
+  /// pass on to their TV over CEC or IR; only Roku TVs have inputs and a tuner of their own.
+  pub fn capabilities(&self) -> Capabilities {
+    let mut capabilities = vec![Capability::InstantReplay, Capability::Info, Capability::Text, Capability::Apps, Capability::Volume];
+
+    if self.is_tv || self.supports_suspend { capabilities.push(Capability::Power); }
+    if self.is_tv { capabilities.push(Capability::TvInputs); }
+    if self.is_tv && self.tuner_type.is_some() { capabilities.push(Capability::Tuner); }
+    if self.supports_find_remote { capabilities.push(Capability::FindRemote); }
+    if self.developer_enabled { capabilities.extend([Capability::Screenshot, Capability::DebugConsole]); }
+
+    capabilities.into_iter().collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const DEVICE_INFO: &str = r#"<?xml version="1.0" encoding="UTF-8" ?>
+<device-info>
+	<udn>29600009-5406-1005-8080-b83e59a1b2c3</udn>
+	<serial-number>X00400ABCDEF</serial-number>
+	<device-id>S00A1234567</device-id>
+	<vendor-name>TCL</vendor-name>
+	<model-name>7105X</model-name>
+	<model-number>7105X</model-number>
+	<model-region>US</model-region>
+	<is-tv>true</is-tv>
+	<is-stick>false</is-stick>
+	<screen-size>55</screen-size>
+	<tuner-type>ATSC</tuner-type>
+	<ui-resolution>1080p</ui-resolution>
+	<supports-ethernet>true</supports-ethernet>
+	<wifi-mac>b8:3e:59:a1:b2:c3</wifi-mac>
+	<has-wifi-5G-support>true</has-wifi-5G-support>
+	<ethernet-mac>b8:3e:59:a1:b2:c4</ethernet-mac>
+	<network-type>ethernet</network-type>
+	<friendly-device-name>Break Room TV</friendly-device-name>
+	<friendly-model-name>TCL•Roku TV</friendly-model-name>
+	<user-device-name>Break Room TV</user-device-name>
+	<user-device-location/>
+	<software-version>11.5.0</software-version>
+	<software-build>4312</software-build>
+	<language>en</language>
+	<country>US</country>
+	<locale>en_US</locale>
+	<time-zone-auto>true</time-zone-auto>
+	<time-zone>US/Pacific</time-zone>
+	<time-zone-name>United States/Pacific</time-zone-name>
+	<time-zone-tz>America/Los_Angeles</time-zone-tz>
+	<time-zone-offset>-480</time-zone-offset>
+	<clock-format>12-hour</clock-format>
+	<uptime>363513</uptime>
+	<power-mode>DisplayOff</power-mode>
+	<supports-find-remote>true</supports-find-remote>
+	<developer-enabled>false</developer-enabled>
+	<trc-version>3.0</trc-version>
+	<keyed-developer-id/>
+</device-info>"#;
+
+  #[test]
+  fn parses_device_info() {
+    let info = RokuDeviceInfo::parse(DEVICE_INFO).unwrap();
+
+    assert_eq!(info.name, "Break Room TV");
+    assert_eq!(info.model_name, "TCL•Roku TV");
+    assert!(info.is_tv);
+    assert_eq!(info.screen_size, Some(55));
+    assert_eq!(info.network_name, None);
+    assert_eq!(info.time_zone_offset, Some(-480));
+    assert_eq!(info.power_mode, Some(RokuPowerMode::DisplayOff));
+    assert!(info.supports_find_remote);
+    assert!(!info.supports_airplay);
+  }
+
+  #[test]
+  fn keeps_unmodelled_fields() {
+    let info = RokuDeviceInfo::parse(DEVICE_INFO).unwrap();
+
+    assert_eq!(info.other.len(), 2);
+    assert_eq!(info.other["trc-version"], "3.0");
+    assert_eq!(info.other["keyed-developer-id"], "");
+  }
+
+  #[test]
+  fn derives_capabilities() {
+    let tv = RokuDeviceInfo::parse(DEVICE_INFO).unwrap().capabilities();
+    assert!(tv.has(Capability::Volume));
+    assert!(tv.has(Capability::Tuner));
+    assert!(tv.has(Capability::FindRemote));
+    assert!(!tv.has(Capability::Screenshot));
+
+    let document = DEVICE_INFO
+      .replace("<is-tv>true</is-tv>", "<is-tv>false</is-tv>")
+      .replace("<supports-find-remote>true</supports-find-remote>", "")
+      .replace("<developer-enabled>false</developer-enabled>", "<developer-enabled>true</developer-enabled>");
+    let player = RokuDeviceInfo::parse(&document).unwrap().capabilities();
+    assert!(player.has(Capability::Volume));
+    assert!(!player.has(Capability::Power));
+    assert!(!player.has(Capability::TvInputs));
+    assert!(!player.has(Capability::FindRemote));
+    assert!(player.has(Capability::Text));
+    assert!(player.has(Capability::Apps));
+    assert!(player.has(Capability::Screenshot));
+    assert!(player.has(Capability::DebugConsole));
+  }
+
+  #[test]
+  fn detects_limited_mode() {
+    assert!(!RokuDeviceInfo::parse(DEVICE_INFO).unwrap().is_limited());
+
+    let limited = DEVICE_INFO.replace("<developer-enabled>", "<ecp-setting-mode>limited</ecp-setting-mode>\n\t<developer-enabled>");
+    assert!(RokuDeviceInfo::parse(&limited).unwrap().is_limited());
+
+    let permissive = limited.replace(">limited<", ">permissive<");
+    assert!(!RokuDeviceInfo::parse(&permissive).unwrap().is_limited());
+  }
+
+  #[test]
+  fn tolerates_unknown_power_modes() {
+    let document = DEVICE_INFO.replace("DisplayOff", "Suspend");
+    assert_eq!(RokuDeviceInfo::parse(&document).unwrap().power_mode, Some(RokuPowerMode::Unknown));
+  }
+}
@@ -0,0 +1,11 @@
+use std::sync::Arc;
+use tokio::sync::mpsc::UnboundedReceiver;
+use crate::devices::{Device, ssdp_search};
+use super::RokuDevice;
+
+/// Searches for Roku devices over SSDP until the returned receiver is dropped.
+pub fn discover() -> UnboundedReceiver<Device> {
+  ssdp_search::search("roku:ecp", |responder| async move {
+    Ok(Arc::new(RokuDevice::query(responder.addr).await?) as Device)
+  })
+}
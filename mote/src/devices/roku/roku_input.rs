@@ -0,0 +1,63 @@
+use crate::devices::{DeviceInput, TvInput};
+use super::RokuKey;
+
+// this is left as an enum because there are other events available to us,
+// namely a KeyUp, KeyDown event, but these are not used yet.
+pub enum RokuInput {
+  KeyPress(RokuKey),
+
+  /// A single character, typed as a `Lit_` key
+  Literal(char),
+
+  Launch(String),
+
+  /// Tune the tuner to a channel number
+  Tune(String),
+
+  SwitchInput(TvInput)
+}
+
+impl RokuInput {
+  /// ECP has no text route, text is typed one `Lit_` keypress per character
+  pub fn from_device_input(input: DeviceInput) -> Vec<RokuInput> {
+    match input {
+      DeviceInput::KeyPress(key) => vec![RokuInput::KeyPress(key.into())],
+      DeviceInput::Text(text) => text.chars().map(RokuInput::Literal).collect(),
+      DeviceInput::Launch(app_id) => vec![RokuInput::Launch(app_id)],
+      DeviceInput::Tune(channel) => vec![RokuInput::Tune(channel)],
+      DeviceInput::SwitchInput(input) => vec![RokuInput::SwitchInput(input)]
+    }
+  }
+}
+
+impl From<&RokuInput> for String {
+  fn from(input: &RokuInput) -> String {
+    // NOTE: the /keydown/:k and /keyup/:k routes also exist but are not generated here
+    
+    match input {
+      RokuInput::KeyPress(key) => format!("keypress/{}", <&'static str>::from(key)),
+      RokuInput::Literal(c) => format!("keypress/Lit_{}", percent_encode(*c)),
+      RokuInput::Launch(app_id) => format!("launch/{}", app_id),
+      RokuInput::Tune(channel) => format!("launch/tvinput.dtv?ch={}", channel.chars().map(percent_encode).collect::<String>()),
+      RokuInput::SwitchInput(input) => format!("keypress/{}", match input {
+        TvInput::Tuner => "InputTuner".to_owned(),
+        TvInput::Hdmi(port) => format!("InputHDMI{}", port),
+        TvInput::Av => "InputAV1".to_owned()
+      })
+    }
+  }
+}
+
+impl From<RokuInput> for String {
+  fn from(input: RokuInput) -> String {
+    String::from(&input)
+  }
+}
+
+/// Percent-encodes a character for use in a URL path segment
+fn percent_encode(c: char) -> String {
+  if c.is_ascii_alphanumeric() || c == '.' { return c.to_string() }
+
+  let mut bytes = [0; 4];
+  c.encode_utf8(&mut bytes).bytes().map(|b| format!("%{:02X}", b)).collect()
+}
@@ -0,0 +1,72 @@
+use crate::devices::Key;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RokuKey {
+  Power,
+  Home,
+
+  Back,
+  Ok,
+
+  PadUp,
+  PadDown,
+  PadLeft,
+  PadRight,
+
+  InstantReplay,
+  Info,
+
+  VolumeUp,
+  VolumeDown,
+  VolumeMute,
+
+  FindRemote
+}
+
+impl From<Key> for RokuKey {
+  fn from(key: Key) -> RokuKey {
+    match key {
+      Key::Power         => RokuKey::Power,
+      Key::Home          => RokuKey::Home,
+      Key::Back          => RokuKey::Back,
+      Key::Ok            => RokuKey::Ok,
+      Key::Up            => RokuKey::PadUp,
+      Key::Down          => RokuKey::PadDown,
+      Key::Left          => RokuKey::PadLeft,
+      Key::Right         => RokuKey::PadRight,
+      Key::InstantReplay => RokuKey::InstantReplay,
+      Key::Info          => RokuKey::Info,
+      Key::VolumeUp      => RokuKey::VolumeUp,
+      Key::VolumeDown    => RokuKey::VolumeDown,
+      Key::VolumeMute    => RokuKey::VolumeMute,
+      Key::FindRemote    => RokuKey::FindRemote
+    }
+  }
+}
+
+impl From<&RokuKey> for &'static str {
+  fn from(key: &RokuKey) -> &'static str {
+    match key {
+      RokuKey::Power         => "power",
+      RokuKey::Home          => "home",
+      RokuKey::Back          => "back",
+      RokuKey::Ok            => "select",
+      RokuKey::PadUp         => "up",
+      RokuKey::PadDown       => "down",
+      RokuKey::PadLeft       => "left",
+      RokuKey::PadRight      => "right",
+      RokuKey::InstantReplay => "instantreplay",
+      RokuKey::Info          => "info",
+      RokuKey::VolumeUp      => "volumeup",
+      RokuKey::VolumeDown    => "volumedown",
+      RokuKey::VolumeMute    => "volumemute",
+      RokuKey::FindRemote    => "findremote"
+    }
+  }
+}
+
+impl From<RokuKey> for &'static str {
+  fn from(key: RokuKey) -> &'static str {
+    (&key).into()
+  }
+}
\ No newline at end of file
@@ -0,0 +1,10 @@
+mod samsung_device;
+mod samsung_device_info;
+mod samsung_discovery;
+mod samsung_key;
+mod samsung_remote;
+
+pub use samsung_device::*;
+pub use samsung_device_info::*;
+pub use samsung_key::*;
+pub use samsung_remote::*;
@@ -0,0 +1,137 @@
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+use futures::{future::BoxFuture, TryFutureExt};
+use tokio::sync::mpsc::UnboundedReceiver;
+use crate::devices::{
+  App, Capabilities, Capability, Device, DeviceInfo, DeviceInput, DeviceType, FormFactor, Model, Network, NetworkType,
+  PowerMode, Product, RemoteDevice, System,
+  discovery::{CLIENT, PAIRING_TIMEOUT, REQUEST_TIMEOUT}, token_store::TokenStore
+};
+use super::{samsung_discovery, samsung_key_code, SamsungDeviceInfo, SamsungRemote};
+
+/// Port of the plain HTTP API, used for device info and by pre-2016 TVs for remote control
+const HTTP_PORT: u16 = 8001;
+
+/// Port of the TLS remote control channel on TVs that issue pairing tokens
+const TLS_PORT: u16 = 8002;
+
+/// A Samsung Tizen TV, controlled over its local WebSocket remote control API.
+#[derive(Debug, Clone)]
+pub struct SamsungDevice {
+  location: SocketAddr,
+  info: DeviceInfo,
+
+  /// Shared between refreshed copies of the device, so polling never drops the connection
+  remote: Arc<SamsungRemote>
+}
+
+impl SamsungDevice {
+  /// Gets device info over HTTP and prepares (but does not open) the remote control channel.
+  pub async fn query(location: SocketAddr) -> anyhow::Result<Self> {
+    let (info, token_auth) = Self::device_info(location).await?;
+
+    let endpoint = match token_auth {
+      true  => format!("wss://{}:{}", location.ip(), TLS_PORT),
+      false => format!("ws://{}:{}", location.ip(), HTTP_PORT)
+    };
+    let remote = SamsungRemote::new(&endpoint, &info.product.serial_number, TokenStore::new("samsung"));
+
+    let mut location = location; location.set_port(HTTP_PORT);
+
+    Ok(SamsungDevice { location, info, remote: Arc::new(remote) })
+  }
+
+  /// Fetches device info, along with whether the TV uses token authentication.
+  async fn device_info(location: SocketAddr) -> anyhow::Result<(DeviceInfo, bool)> {
+    let response =
+      CLIENT
+        .get(format!("http://{}:{}/api/v2/", location.ip(), HTTP_PORT))
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+
+    let SamsungDeviceInfo { id, device } = serde_json::from_str(&response)?;
+
+    let network_type = match device.network_type.as_deref() {
+      Some("wireless") => NetworkType::WiFi,
+      Some("wired") => NetworkType::Ethernet,
+      _ => NetworkType::Unknown
+    };
+
+    let info = DeviceInfo {
+      name: device.name,
+      product: Product {
+        vendor: "Samsung".into(),
+        // Samsung does not expose a serial number, the device id is the stable identifier
+        serial_number: id,
+        model: Model {
+          name: device.model_name.clone(),
+          alternate_name: device.model,
+          number: device.model_name
+        },
+        form_factor: FormFactor::Tv
+      },
+      network: Network {
+        network_type,
+        network_name: String::new(),
+        mac_address: device.wifi_mac.unwrap_or_default()
+      },
+      system: System {
+        software_version: device.os,
+        power_mode: match device.power_state.as_deref() {
+          Some("on") => Some(PowerMode::On),
+          Some("standby") => Some(PowerMode::Standby),
+          _ => None
+        },
+        resolution: device.resolution,
+        developer_mode: device.developer_mode.map(|mode| mode == "1"),
+        ..System::default()
+      },
+      now_playing: None,
+      features: vec![]
+    };
+
+    Ok((info, device.token_auth_support == "true"))
+  }
+}
+
+impl RemoteDevice for SamsungDevice {
+  fn discover() -> UnboundedReceiver<Device> { samsung_discovery::discover() }
+
+  fn device_type(&self) -> DeviceType { DeviceType::Samsung }
+
+  fn location(&self) -> Option<SocketAddr> { Some(self.location) }
+
+  fn info(&self) -> &DeviceInfo { &self.info }
+
+  fn capabilities(&self) -> Capabilities {
+    Capabilities::from([Capability::Power, Capability::Volume, Capability::InstantReplay, Capability::Info, Capability::Apps])
+  }
+
+  fn send_input(&self, input: DeviceInput) -> BoxFuture<'_, crate::Result<()>> {
+    Box::pin(async move {
+      match input {
+        DeviceInput::KeyPress(key) => self.remote.send_key(samsung_key_code(key).ok_or_else(|| self.unsupported_key(key))?).await,
+        DeviceInput::Text(_) => Err(anyhow::anyhow!("Samsung TVs do not accept typed text over the remote control channel")),
+        DeviceInput::Tune(_) | DeviceInput::SwitchInput(_) => Err(anyhow::anyhow!("Samsung TVs cannot tune channels or switch inputs from mote")),
+        DeviceInput::Launch(app_id) => self.remote.launch(&app_id).await
+      }
+    }.err_into())
+  }
+
+  fn input_timeout(&self) -> Duration {
+    if self.remote.is_paired() { REQUEST_TIMEOUT } else { PAIRING_TIMEOUT }
+  }
+
+  fn query_state(&self) -> BoxFuture<'_, crate::Result<Device>> {
+    Box::pin(async move {
+      let (info, _) = Self::device_info(self.location).await?;
+      Ok(Arc::new(SamsungDevice { info, ..self.clone() }) as Device)
+    })
+  }
+
+  fn apps(&self) -> BoxFuture<'_, anyhow::Result<Vec<App>>> {
+    Box::pin(self.remote.installed_apps())
+  }
+}
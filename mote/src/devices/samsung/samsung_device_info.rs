@@ -0,0 +1,43 @@
+/// Samsung-specific representation of device info, as served by `http://<tv>:8001/api/v2/`
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct SamsungDeviceInfo {
+  /// Unique identifier of the TV, stable across IP changes
+  pub id: String,
+  pub device: SamsungDeviceDetails
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SamsungDeviceDetails {
+  /// Human-readable device name
+  pub name: String,
+
+  /// Human-readable model name
+  pub model_name: String,
+
+  /// Internal model name
+  pub model: String,
+
+  /// `wireless` or `wired`
+  pub network_type: Option<String>,
+
+  pub wifi_mac: Option<String>,
+
+  /// `on` or `standby`, on models that keep answering in standby
+  #[serde(rename = "PowerState")]
+  pub power_state: Option<String>,
+
+  /// e.g. `3840x2160`
+  pub resolution: Option<String>,
+
+  /// `Tizen` on every model mote supports
+  #[serde(rename = "OS")]
+  pub os: Option<String>,
+
+  /// `1` when developer mode is on
+  pub developer_mode: Option<String>,
+
+  /// Whether the TV issues pairing tokens (2016+ models), which also means it expects TLS
+  #[serde(rename = "TokenAuthSupport", default)]
+  pub token_auth_support: String
+}
@@ -0,0 +1,11 @@
+use std::sync::Arc;
+use tokio::sync::mpsc::UnboundedReceiver;
+use crate::devices::{Device, ssdp_search};
+use super::SamsungDevice;
+
+/// Searches for Samsung TVs over SSDP until the returned receiver is dropped.
+pub fn discover() -> UnboundedReceiver<Device> {
+  ssdp_search::search("urn:samsung.com:device:RemoteControlReceiver:1", |responder| async move {
+    Ok(Arc::new(SamsungDevice::query(responder.addr).await?) as Device)
+  })
+}
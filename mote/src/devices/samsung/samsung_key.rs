@@ -0,0 +1,21 @@
+use crate::devices::Key;
+
+/// Maps mote's keys onto the `KEY_*` codes understood by Samsung's remote control channel.
+pub fn samsung_key_code(key: Key) -> Option<&'static str> {
+  Some(match key {
+    Key::Power         => "KEY_POWER",
+    Key::Home          => "KEY_HOME",
+    Key::Back          => "KEY_RETURN",
+    Key::Ok            => "KEY_ENTER",
+    Key::Up            => "KEY_UP",
+    Key::Down          => "KEY_DOWN",
+    Key::Left          => "KEY_LEFT",
+    Key::Right         => "KEY_RIGHT",
+    Key::InstantReplay => "KEY_REWIND",
+    Key::Info          => "KEY_INFO",
+    Key::VolumeUp      => "KEY_VOLUP",
+    Key::VolumeDown    => "KEY_VOLDOWN",
+    Key::VolumeMute    => "KEY_MUTE",
+    Key::FindRemote    => return None
+  })
+}
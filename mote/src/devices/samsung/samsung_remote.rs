@@ -0,0 +1,288 @@
+use std::fmt::{Debug, Formatter};
+use anyhow::{anyhow, bail};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use futures::{SinkExt, StreamExt};
+use serde_json::Value;
+use tokio::{net::TcpStream, sync::Mutex, time::timeout};
+use tokio_tungstenite::{connect_async_tls_with_config, tungstenite::Message, Connector, MaybeTlsStream, WebSocketStream};
+use crate::{devices::{discovery::REQUEST_TIMEOUT, token_store::TokenStore, App}, Error};
+
+/// Name mote introduces itself with, shown on the TV's pairing prompt
+const APP_NAME: &str = "mote";
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// First message the TV sends on a channel, telling us whether we are allowed in
+#[derive(serde::Deserialize)]
+struct ChannelEvent {
+  event: String,
+  #[serde(default)]
+  data: Option<ChannelEventData>
+}
+
+#[derive(serde::Deserialize)]
+struct ChannelEventData {
+  token: Option<String>
+}
+
+/// Connection to a Samsung TV's local WebSocket remote control channel.
+///
+/// The connection is opened lazily on the first key and kept for the following ones. On the
+/// first connection the TV asks the user to allow mote; the token it then issues is stored,
+/// so later connections go straight through.
+pub struct SamsungRemote {
+  /// Channel URL without the token parameter
+  url: String,
+
+  /// Which TV the stored token belongs to
+  device_id: String,
+
+  tokens: TokenStore,
+  socket: Mutex<Option<Socket>>
+}
+
+impl Debug for SamsungRemote {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("SamsungRemote").field("url", &self.url).field("device_id", &self.device_id).finish()
+  }
+}
+
+impl SamsungRemote {
+  /// `endpoint` is the scheme, host and port of the TV's API, e.g. `wss://192.168.1.20:8002`.
+  pub fn new(endpoint: &str, device_id: &str, tokens: TokenStore) -> Self {
+    SamsungRemote {
+      url: format!("{}/api/v2/channels/samsung.remote.control?name={}", endpoint, STANDARD.encode(APP_NAME)),
+      device_id: device_id.to_owned(),
+      tokens,
+      socket: Mutex::new(None)
+    }
+  }
+
+  /// Whether the TV has issued a token, so connecting will not wait on its prompt
+  pub fn is_paired(&self) -> bool { self.tokens.get(&self.device_id).is_some() }
+
+  /// Presses a key given as a `KEY_*` code.
+  pub async fn send_key(&self, code: &str) -> anyhow::Result<()> {
+    let message = serde_json::json!({
+      "method": "ms.remote.control",
+      "params": {
+        "Cmd": "Click",
+        "DataOfCmd": code,
+        "Option": "false",
+        "TypeOfRemote": "SendRemoteKey"
+      }
+    }).to_string();
+
+    self.send(message).await
+  }
+
+  /// Launches an installed app by its id (e.g. `3201907018807` for Netflix).
+  pub async fn launch(&self, app_id: &str) -> anyhow::Result<()> {
+    let message = serde_json::json!({
+      "method": "ms.channel.emit",
+      "params": {
+        "event": "ed.apps.launch",
+        "to": "host",
+        "data": { "appId": app_id, "action_type": "DEEP_LINK" }
+      }
+    }).to_string();
+
+    self.send(message).await
+  }
+
+  /// Lists the installed apps, which the TV answers with an `ed.installedApp.get` event.
+  pub async fn installed_apps(&self) -> anyhow::Result<Vec<App>> {
+    let message = serde_json::json!({
+      "method": "ms.channel.emit",
+      "params": { "event": "ed.installedApp.get", "to": "host" }
+    }).to_string();
+
+    let mut socket = self.socket.lock().await;
+    let open = self.deliver(&mut socket, message).await?;
+
+    // older TVs ignore the request rather than refuse it
+    let answer = timeout(REQUEST_TIMEOUT, next_event(open, "ed.installedApp.get")).await.map_err(|_| Error::Timeout)??;
+
+    Ok(
+      answer["data"]["data"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|app| Some(App {
+          id: app["appId"].as_str()?.to_owned(),
+          name: app["name"].as_str()?.to_owned()
+        }))
+        .collect()
+    )
+  }
+
+  /// Sends a message on the channel, connecting first if needed.
+  async fn send(&self, message: String) -> anyhow::Result<()> {
+    self.deliver(&mut *self.socket.lock().await, message).await.map(|_| ())
+  }
+
+  /// Sends a message on the kept connection, or a new one, returning the connection it went out on.
+  async fn deliver<'a>(&self, socket: &'a mut Option<Socket>, message: String) -> anyhow::Result<&'a mut Socket> {
+    // the TV drops idle connections, so a send on a kept connection may fail once; reconnect and retry
+    if let Some(mut open) = socket.take() {
+      if open.send(Message::Text(message.clone())).await.is_ok() { return Ok(socket.insert(open)) }
+    }
+
+    let mut fresh = self.connect().await?;
+    fresh.send(Message::Text(message)).await?;
+    Ok(socket.insert(fresh))
+  }
+
+  /// Opens the channel and waits for the TV to let us in, storing any token it issues.
+  async fn connect(&self) -> anyhow::Result<Socket> {
+    let url = match self.tokens.get(&self.device_id) {
+      Some(token) => format!("{}&token={}", self.url, token),
+      None => self.url.clone()
+    };
+
+    // TVs serve a self-signed certificate
+    let tls = native_tls::TlsConnector::builder().danger_accept_invalid_certs(true).build()?;
+    let (mut socket, _) = connect_async_tls_with_config(url, None, false, Some(Connector::NativeTls(tls))).await?;
+
+    while let Some(message) = socket.next().await {
+      let Message::Text(text) = message? else { continue };
+      let ChannelEvent { event, data } = serde_json::from_str(&text)?;
+
+      match event.as_str() {
+        "ms.channel.connect" => {
+          if let Some(token) = data.and_then(|d| d.token) {
+            self.tokens.set(&self.device_id, &token)?;
+          }
+          return Ok(socket)
+        },
+        // also what a stored token the TV no longer accepts gets
+        "ms.channel.unauthorized" => return Err(Error::Permission("the TV did not allow mote, accept the prompt on screen".into()).into()),
+        "ms.channel.timeOut" => bail!("timed out waiting for the pairing prompt to be accepted on the TV"),
+        _ => continue
+      }
+    }
+
+    Err(anyhow!("TV closed the connection while pairing"))
+  }
+}
+
+/// Reads the channel until the TV emits `event`, skipping anything else it sends meanwhile.
+async fn next_event(socket: &mut Socket, event: &str) -> anyhow::Result<Value> {
+  while let Some(message) = socket.next().await {
+    let Message::Text(text) = message? else { continue };
+    let received: Value = serde_json::from_str(&text)?;
+    if received["event"] == event { return Ok(received) }
+  }
+
+  Err(anyhow!("TV closed the connection before sending {}", event))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::path::PathBuf;
+  use tokio::net::TcpListener;
+  use tokio_tungstenite::{accept_hdr_async, tungstenite::handshake::server::{Request, Response}};
+
+  fn temporary_store(name: &str) -> (TokenStore, PathBuf) {
+    let path = std::env::temp_dir().join(format!("mote-test-{}-{}.json", name, std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    (TokenStore::at(path.clone()), path)
+  }
+
+  /// Stands in for a TV: accepts one connection, reports the request URI, greets with
+  /// `greeting` and reports the first message received, answering it with `reply` if given.
+  #[allow(clippy::result_large_err)] // the handshake callback's signature is tungstenite's
+  async fn stand_in(greeting: &'static str, reply: Option<&'static str>) -> (String, tokio::task::JoinHandle<(String, Option<String>)>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let endpoint = format!("ws://{}", listener.local_addr().unwrap());
+
+    let tv = tokio::spawn(async move {
+      let (stream, _) = listener.accept().await.unwrap();
+
+      let mut uri = String::new();
+      let mut socket = accept_hdr_async(stream, |request: &Request, response: Response| {
+        uri = request.uri().to_string();
+        Ok(response)
+      }).await.unwrap();
+
+      socket.send(Message::Text(greeting.into())).await.unwrap();
+
+      let received = match socket.next().await {
+        Some(Ok(Message::Text(text))) => Some(text),
+        _ => None
+      };
+
+      if let Some(reply) = reply { socket.send(Message::Text(reply.into())).await.unwrap(); }
+
+      (uri, received)
+    });
+
+    (endpoint, tv)
+  }
+
+  #[tokio::test]
+  async fn pairs_and_persists_token() {
+    let (tokens, path) = temporary_store("samsung-pair");
+    let (endpoint, tv) = stand_in(r#"{"event":"ms.channel.connect","data":{"token":"12345678"}}"#, None).await;
+
+    let remote = SamsungRemote::new(&endpoint, "uuid:tv", tokens.clone());
+    remote.send_key("KEY_UP").await.unwrap();
+
+    let (uri, received) = tv.await.unwrap();
+    assert!(uri.contains("name=bW90ZQ=="));
+    assert!(!uri.contains("token="));
+
+    let received: serde_json::Value = serde_json::from_str(&received.unwrap()).unwrap();
+    assert_eq!(received["method"], "ms.remote.control");
+    assert_eq!(received["params"]["DataOfCmd"], "KEY_UP");
+
+    assert_eq!(tokens.get("uuid:tv").as_deref(), Some("12345678"));
+    let _ = std::fs::remove_file(path);
+  }
+
+  #[tokio::test]
+  async fn reconnects_with_stored_token() {
+    let (tokens, path) = temporary_store("samsung-token");
+    tokens.set("uuid:tv", "87654321").unwrap();
+
+    let (endpoint, tv) = stand_in(r#"{"event":"ms.channel.connect","data":{}}"#, None).await;
+
+    let remote = SamsungRemote::new(&endpoint, "uuid:tv", tokens);
+    remote.send_key("KEY_HOME").await.unwrap();
+
+    let (uri, _) = tv.await.unwrap();
+    assert!(uri.ends_with("&token=87654321"));
+    let _ = std::fs::remove_file(path);
+  }
+
+  #[tokio::test]
+  async fn reports_declined_pairing() {
+    let (tokens, path) = temporary_store("samsung-declined");
+    let (endpoint, _tv) = stand_in(r#"{"event":"ms.channel.unauthorized"}"#, None).await;
+
+    let remote = SamsungRemote::new(&endpoint, "uuid:tv", tokens);
+    let e = remote.send_key("KEY_POWER").await.unwrap_err();
+    assert!(matches!(Error::from(e), Error::Permission(_)));
+    let _ = std::fs::remove_file(path);
+  }
+
+  #[tokio::test]
+  async fn lists_installed_apps() {
+    let (tokens, path) = temporary_store("samsung-apps");
+    let (endpoint, tv) = stand_in(
+      r#"{"event":"ms.channel.connect","data":{}}"#,
+      Some(r#"{"event":"ed.installedApp.get","data":{"data":[{"appId":"3201907018807","name":"Netflix","app_type":2}]}}"#)
+    ).await;
+
+    let remote = SamsungRemote::new(&endpoint, "uuid:tv", tokens);
+    let apps = remote.installed_apps().await.unwrap();
+    assert_eq!(apps, vec![App { id: "3201907018807".into(), name: "Netflix".into() }]);
+
+    let (_, received) = tv.await.unwrap();
+    let received: serde_json::Value = serde_json::from_str(&received.unwrap()).unwrap();
+    assert_eq!(received["params"]["event"], "ed.installedApp.get");
+    let _ = std::fs::remove_file(path);
+  }
+}
@@ -0,0 +1,119 @@
+use std::future::Future;
+use anyhow::anyhow;
+use tokio::{spawn, sync::{mpsc::{unbounded_channel, UnboundedSender}, oneshot}, time::timeout};
+use crate::Error;
+use super::{Device, DeviceInput};
+
+/// An input waiting its turn, the device to deliver it to, and where to report how its delivery went
+type Queued = (Device, DeviceInput, oneshot::Sender<crate::Result<()>>);
+
+/// Delivers inputs to one device one at a time, in the order they were queued. Sending each
+/// input from its own task lets the requests race, so `Down, Down, Select` can arrive as
+/// `Down, Select, Down`.
+#[derive(Debug, Clone)]
+pub struct SendQueue {
+  inputs: UnboundedSender<Queued>
+}
+
+impl SendQueue {
+  /// Starts delivering. Inputs already queued are still delivered once every handle to the
+  /// queue is dropped. An input the device has not acknowledged within its
+  /// `RemoteDevice::input_timeout` fails with `Error::Timeout`, so one stuck send does not hold
+  /// up the inputs behind it.
+  pub fn new() -> Self {
+    let (inputs, mut queued) = unbounded_channel::<Queued>();
+
+    spawn(async move {
+      while let Some((device, input, done)) = queued.recv().await {
+        let delivered = timeout(device.input_timeout(), device.send_input(input)).await;
+        let _ = done.send(delivered.unwrap_or(Err(Error::Timeout)));
+      }
+    });
+
+    SendQueue { inputs }
+  }
+
+  /// Queues an input for `device` straight away, returning a future that resolves once the
+  /// device has acknowledged it (or failed to). The future need not be awaited for the input to
+  /// be sent. Pass the device's latest state each time, so an input goes to where the device is
+  /// now rather than where it was when the queue started.
+  pub fn send(&self, device: Device, input: DeviceInput) -> impl Future<Output = crate::Result<()>> + Send + 'static {
+    let (done, result) = oneshot::channel();
+    let queued = self.inputs.send((device, input, done)).is_ok();
+
+    async move {
+      match queued {
+        true => result.await.unwrap_or_else(|_| Err(anyhow!("The send queue stopped before delivering the input").into())),
+        false => Err(anyhow!("The send queue has stopped").into())
+      }
+    }
+  }
+}
+
+impl Default for SendQueue {
+  fn default() -> Self { Self::new() }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{net::SocketAddr, sync::{Arc, Mutex}, time::Duration};
+  use futures::future::{join_all, BoxFuture};
+  use tokio::sync::mpsc::UnboundedReceiver;
+  use crate::devices::{Capabilities, DeviceInfo, DeviceType, Key, RemoteDevice};
+  use super::*;
+
+  /// Takes longer to acknowledge the earlier keys, so unqueued sends would finish out of order,
+  /// and never acknowledges Home
+  #[derive(Debug, Default)]
+  struct SlowDevice { info: DeviceInfo, delivered: Mutex<Vec<DeviceInput>> }
+
+  impl RemoteDevice for SlowDevice {
+    fn discover() -> UnboundedReceiver<Device> { unbounded_channel().1 }
+    fn device_type(&self) -> DeviceType { DeviceType::Roku }
+    fn location(&self) -> Option<SocketAddr> { None }
+    fn info(&self) -> &DeviceInfo { &self.info }
+    fn capabilities(&self) -> Capabilities { Capabilities::default() }
+
+    fn send_input(&self, input: DeviceInput) -> BoxFuture<'_, crate::Result<()>> {
+      Box::pin(async move {
+        let delay = match input {
+          DeviceInput::KeyPress(Key::Home) => return futures::future::pending().await,
+          DeviceInput::KeyPress(Key::Down) => 30,
+          _ => 0
+        };
+        tokio::time::sleep(Duration::from_millis(delay)).await;
+        self.delivered.lock().unwrap().push(input);
+        Ok(())
+      })
+    }
+
+    fn query_state(&self) -> BoxFuture<'_, crate::Result<Device>> {
+      Box::pin(async move { Err(anyhow!("not queried").into()) })
+    }
+  }
+
+  #[tokio::test]
+  async fn delivers_in_order() {
+    let device = Arc::new(SlowDevice::default());
+    let queue = SendQueue::new();
+
+    let sent: Vec<_> = [Key::Down, Key::Down, Key::Ok].into_iter().map(|key| spawn(queue.send(device.clone(), key.into()))).collect();
+    for result in join_all(sent).await { result.unwrap().unwrap(); }
+
+    let delivered = device.delivered.lock().unwrap().clone();
+    assert_eq!(delivered, vec![Key::Down.into(), Key::Down.into(), DeviceInput::from(Key::Ok)]);
+  }
+
+  #[tokio::test(start_paused = true)]
+  async fn gives_up_on_stuck_inputs() {
+    let device = Arc::new(SlowDevice::default());
+    let queue = SendQueue::new();
+
+    let stuck = queue.send(device.clone(), Key::Home.into());
+    let next = queue.send(device.clone(), Key::Ok.into());
+
+    assert!(matches!(stuck.await, Err(Error::Timeout)));
+    next.await.unwrap();
+    assert_eq!(device.delivered.lock().unwrap().clone(), vec![DeviceInput::from(Key::Ok)]);
+  }
+}
@@ -0,0 +1,75 @@
+use std::{future::Future, net::SocketAddr, time::Duration};
+use ssdp::{FieldMap, header::{HeaderMut, HeaderRef, Location, Man, MX, ST}, message::{SearchRequest, Multicast}};
+use futures::future::join_all;
+use tokio::{runtime::Handle, sync::mpsc::{unbounded_channel, UnboundedReceiver}};
+use super::Device;
+
+/// How long to wait before searching again after a search could not be sent, e.g. with no network up
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// A device that answered an SSDP search
+#[derive(Debug, Clone)]
+pub struct Responder {
+  pub addr: SocketAddr,
+
+  /// URL of the responder's UPnP description, if it sent one
+  #[cfg_attr(not(feature = "lg"), allow(dead_code))]
+  pub location: Option<String>
+}
+
+/// Repeatedly searches for SSDP search target `target`, turning each new responder into a
+/// device with `query`, until the returned receiver is dropped. Responders that cannot be
+/// queried are skipped.
+///
+/// SSDP searches block, so they run on their own thread rather than tying up the async runtime;
+/// that also means quitting never has to wait on a search in progress.
+pub fn search<F, Fut>(target: &'static str, query: F) -> UnboundedReceiver<Device>
+where
+  F: Fn(Responder) -> Fut + Send + 'static,
+  Fut: Future<Output = anyhow::Result<Device>> + Send + 'static
+{
+  let (tx, rx) = unbounded_channel::<Device>();
+  let runtime = Handle::current();
+  
+  std::thread::spawn(move || {
+    // This is not fully correct. In principle devices could change (or worse, swap!) IP addresses during the lifetime
+    // of this utility.  This is good enough for now.  Submit a PR if you like.
+    let mut devices = vec![];
+
+    let mut request = {
+      let mut rq = SearchRequest::new();
+      rq.set(Man);
+      rq.set(MX(0));
+      rq.set(ST::Target(FieldMap::new(target).expect("Valid search target")));
+      rq
+    };
+
+    while !tx.is_closed() {
+      // lookup each device independently for lower latency
+      let mut fut = vec![];
+
+      let responses = match request.multicast() {
+        Ok(responses) => responses,
+        Err(_) => { std::thread::sleep(RETRY_DELAY); continue }
+      };
+
+      for (response, addr) in responses {
+        if devices.contains(&addr) { continue; }
+        devices.push(addr);
+
+        let tx = tx.clone();
+        let location = response.get::<Location>().map(|l| l.to_string());
+        let device = query(Responder { addr, location });
+        fut.push(
+          runtime.spawn(async move {
+            if let Ok(device) = device.await { let _ = tx.send(device); }
+          })
+        );
+      }
+
+      runtime.spawn(async move { join_all(fut).await });
+    }
+  });
+
+  rx
+}
@@ -0,0 +1,45 @@
+use std::{collections::HashMap, fs, path::PathBuf};
+use crate::config::config_dir;
+
+/// Remembers the pairing tokens TVs hand out, so a TV only has to be approved once.
+/// Tokens are kept per platform in a small JSON file under the user's config directory.
+#[derive(Debug, Clone)]
+pub struct TokenStore {
+  path: PathBuf
+}
+
+impl TokenStore {
+  /// The store for one platform, e.g. `TokenStore::new("samsung")`
+  pub fn new(platform: &str) -> Self {
+    Self::at(config_dir().join(format!("{}-tokens.json", platform)))
+  }
+
+  /// A store backed by an explicit file
+  pub fn at(path: PathBuf) -> Self {
+    TokenStore { path }
+  }
+
+  /// The token previously issued by a device, if any
+  pub fn get(&self, device_id: &str) -> Option<String> {
+    self.load().remove(device_id)
+  }
+
+  /// Records a token issued by a device, replacing any older one.
+  pub fn set(&self, device_id: &str, token: &str) -> anyhow::Result<()> {
+    let mut tokens = self.load();
+    tokens.insert(device_id.to_owned(), token.to_owned());
+
+    if let Some(parent) = self.path.parent() { fs::create_dir_all(parent)?; }
+    fs::write(&self.path, serde_json::to_string_pretty(&tokens)?)?;
+
+    Ok(())
+  }
+
+  /// A missing or unreadable file just means nothing has been paired yet
+  fn load(&self) -> HashMap<String, String> {
+    fs::read_to_string(&self.path)
+      .ok()
+      .and_then(|contents| serde_json::from_str(&contents).ok())
+      .unwrap_or_default()
+  }
+}
@@ -0,0 +1,131 @@
+use std::{error::Error as StdError, io};
+use reqwest::StatusCode;
+
+/// What caused an error, kept for its message
+type Cause = Box<dyn StdError + Send + Sync>;
+
+/// Why finding a device, querying it or sending it input failed, so callers can tell a device
+/// that is unreachable from one that refuses them.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+  /// The device did not answer in time
+  #[error("The device did not answer in time")]
+  Timeout,
+
+  /// The device could not be reached, or the connection to it broke
+  #[error("Could not reach the device: {0}")]
+  Transport(#[source] Cause),
+
+  /// The device answered, but will not take requests from this computer, saying why
+  #[error("The device refused the request ({0})")]
+  Permission(String),
+
+  /// The device answered with any other error status
+  #[error("The device answered {0}")]
+  Status(StatusCode),
+
+  /// The device's answer could not be understood
+  #[error("Could not understand the device's answer: {0}")]
+  Parse(#[source] Cause),
+
+  /// No device matched the selector within the time allowed
+  #[error("No device named {selector} was found within {seconds}s")]
+  NotFound { selector: String, seconds: u64 },
+
+  /// Anything else, as the platform described it
+  #[error(transparent)]
+  Other(anyhow::Error)
+}
+
+/// The result of finding, querying or controlling a device
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+  /// The error for an HTTP error status
+  pub fn from_status(status: StatusCode) -> Self {
+    match status {
+      StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Error::Permission(status.to_string()),
+      _ => Error::Status(status)
+    }
+  }
+
+  /// What the user can do about it, for errors with a usual fix
+  pub fn hint(&self) -> Option<&'static str> {
+    match self {
+      Error::Timeout | Error::Transport(_) => Some("check the device is on and on the same network as this computer"),
+      Error::Permission(_) => Some("allow mote on the device: accept its pairing prompt, or on a Roku set Control by mobile apps > Network access to Permissive"),
+      Error::NotFound { .. } => Some("check the name, or use the device's IP address"),
+      _ => None
+    }
+  }
+}
+
+impl From<reqwest::Error> for Error {
+  fn from(e: reqwest::Error) -> Self {
+    match e.status() {
+      _ if e.is_timeout() => Error::Timeout,
+      Some(status) => Error::from_status(status),
+      None if e.is_decode() => Error::Parse(e.into()),
+      None => Error::Transport(e.into())
+    }
+  }
+}
+
+impl From<io::Error> for Error {
+  fn from(e: io::Error) -> Self {
+    match e.kind() {
+      io::ErrorKind::TimedOut => Error::Timeout,
+      _ => Error::Transport(e.into())
+    }
+  }
+}
+
+impl From<serde_xml_rs::Error> for Error {
+  fn from(e: serde_xml_rs::Error) -> Self { Error::Parse(e.into()) }
+}
+
+impl From<serde_json::Error> for Error {
+  fn from(e: serde_json::Error) -> Self { Error::Parse(e.into()) }
+}
+
+/// Recovers the typed error from inside an `anyhow` error where there is one, so failures
+/// passed up through platform code that uses `anyhow` keep their kind.
+impl From<anyhow::Error> for Error {
+  fn from(e: anyhow::Error) -> Self {
+    let e = match e.downcast::<Error>() { Ok(e) => return e, Err(e) => e };
+    let e = match e.downcast::<reqwest::Error>() { Ok(e) => return e.into(), Err(e) => e };
+    let e = match e.downcast::<io::Error>() { Ok(e) => return e.into(), Err(e) => e };
+
+    Error::Other(e)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn refusals_are_permission_errors() {
+    assert!(matches!(Error::from_status(StatusCode::FORBIDDEN), Error::Permission(_)));
+    assert!(matches!(Error::from_status(StatusCode::UNAUTHORIZED), Error::Permission(_)));
+    assert!(matches!(Error::from_status(StatusCode::NOT_FOUND), Error::Status(StatusCode::NOT_FOUND)));
+    assert!(Error::from_status(StatusCode::FORBIDDEN).hint().unwrap().contains("Control by mobile apps"));
+  }
+
+  #[test]
+  fn keeps_the_kind_through_anyhow() {
+    let e = anyhow::Error::from(Error::Timeout).context("Sending Home");
+    assert!(matches!(Error::from(e), Error::Timeout));
+
+    let e = anyhow::Error::from(io::Error::new(io::ErrorKind::ConnectionRefused, "refused"));
+    assert!(matches!(Error::from(e), Error::Transport(_)));
+
+    assert!(matches!(Error::from(anyhow::anyhow!("no such key")), Error::Other(_)));
+  }
+
+  #[test]
+  fn parse_failures_are_parse_errors() {
+    let e = serde_json::from_str::<u32>("nope").unwrap_err();
+    assert!(matches!(Error::from(e), Error::Parse(_)));
+  }
+}
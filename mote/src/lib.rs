@@ -0,0 +1,34 @@
+//! Discover and control TVs and streaming players on the local network, the library behind
+//! `cargo mote`.
+//!
+//! [`discover`] streams every device it finds, while [`discover_for`] and [`find_device`]
+//! search for a bounded time. Each is a [`Device`], a shared [`RemoteDevice`] that takes
+//! platform-independent [`DeviceInput`]s (such as [`Key`] presses) and reports its
+//! [`DeviceInfo`] and [`Capabilities`].
+//!
+//! ```no_run
+//! # async fn run() -> mote::Result<()> {
+//! use std::time::Duration;
+//! use mote::{find_device, Key};
+//!
+//! let tv = find_device("Living Room", Duration::from_secs(5)).await?;
+//! tv.send_input(Key::Home.into()).await?;
+//! println!("{} is on {:?}", tv.info().name, tv.info().system.software_version);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Roku is always supported, over ECP; the other platforms are behind the `samsung`, `lg`,
+//! `android`, `cec` and `kodi` features. Finding, querying and controlling a device fail with an
+//! [`Error`] saying whether the device was unreachable, refused or answered something unexpected,
+//! with a [`hint`](Error::hint) for the user where there is a usual fix.
+
+pub mod config;
+pub mod devices;
+mod error;
+
+pub use error::{Error, Result};
+pub use devices::{
+  discover, discover_for, discover_until, find_device,
+  App, AppState, Capabilities, Capability, Device, DeviceInfo, DeviceInput, DeviceType, Key, RemoteDevice, SendQueue
+};
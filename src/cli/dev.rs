@@ -0,0 +1,138 @@
+use std::{fs::{self, OpenOptions}, io::{Cursor, Write}, path::{Path, PathBuf}};
+use anyhow::{anyhow, bail, Context};
+use zip::{write::FileOptions, CompressionMethod, ZipWriter};
+use mote::{config::Config, devices::{roku::{connect_debug_console, RokuDeveloper}, DeviceType}};
+use super::{target, DevArgs, DevCommand};
+
+pub async fn run(args: DevArgs, config: &Config) -> anyhow::Result<()> {
+  let device = target::find(args.device.as_deref(), |device| {
+    device.device_type() == DeviceType::Roku && device.info().system.developer_mode == Some(true)
+  }).await?;
+
+  if device.device_type() != DeviceType::Roku { bail!("{} is not a Roku", device.info().name) }
+  let host = device.location().ok_or_else(|| anyhow!("{} has no network address", device.info().name))?.ip();
+  let developer = || RokuDeveloper::new(host, &config.developer);
+
+  match args.command {
+    DevCommand::Install { path } => {
+      let archive = archive(&path)?;
+      report(developer()?.install(archive).await?);
+      println!("Installed {} on {}", path.display(), device.info().name);
+    },
+
+    DevCommand::Delete => {
+      report(developer()?.delete().await?);
+      println!("Deleted the sideloaded channel from {}", device.info().name);
+    },
+
+    DevCommand::Logs { filter, output } => {
+      let mut file = match &output {
+        Some(path) => Some(OpenOptions::new().create(true).append(true).open(path).with_context(|| format!("Failed to open {}", path.display()))?),
+        None => None
+      };
+
+      let mut lines = connect_debug_console(host).await?;
+      eprintln!("Connected to {}'s debug console, Ctrl-C to stop", device.info().name);
+
+      while let Some(line) = lines.recv().await {
+        if filter.as_ref().is_some_and(|filter| !line.matches(filter)) { continue }
+
+        println!("{}", line);
+        if let Some(file) = &mut file { writeln!(file, "{}", line)?; }
+      }
+
+      eprintln!("The debug console hung up");
+    },
+
+    DevCommand::Package { name, output, signing_password } => {
+      let Some(signing_password) = signing_password.or_else(|| config.developer.signing_password.clone()) else {
+        bail!("No signing password given, pass --signing-password or set `signing_password` under [developer] in {}", Config::path().display())
+      };
+
+      let output = output.unwrap_or_else(|| PathBuf::from(format!("{}.pkg", name.replace('/', "-"))));
+      let package = developer()?.package(&name, &signing_password).await?;
+      fs::write(&output, package).with_context(|| format!("Failed to write {}", output.display()))?;
+      println!("Saved {}", output.display());
+    }
+  }
+
+  Ok(())
+}
+
+/// Prints what the installer had to say
+fn report(messages: Vec<String>) {
+  for message in messages { println!("{}", message) }
+}
+
+/// The zipped channel at `path`, zipping it first if it is a directory.
+fn archive(path: &Path) -> anyhow::Result<Vec<u8>> {
+  if !path.is_dir() { return fs::read(path).with_context(|| format!("Failed to read {}", path.display())) }
+
+  if !path.join("manifest").is_file() {
+    bail!("{} has no manifest, point at the channel's root directory", path.display())
+  }
+
+  let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+  add_directory(&mut zip, path, "")?;
+  Ok(zip.finish()?.into_inner())
+}
+
+/// Adds everything under `directory` to the zip beneath `prefix`, skipping hidden files such as `.git`.
+fn add_directory(zip: &mut ZipWriter<Cursor<Vec<u8>>>, directory: &Path, prefix: &str) -> anyhow::Result<()> {
+  let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+  let mut entries = fs::read_dir(directory)?.collect::<Result<Vec<_>, _>>()?;
+  entries.sort_by_key(|entry| entry.file_name());
+
+  for entry in entries {
+    let file_name = entry.file_name().to_string_lossy().into_owned();
+    if file_name.starts_with('.') { continue }
+
+    // zip entries always use forward slashes
+    let name = format!("{}{}", prefix, file_name);
+
+    if entry.file_type()?.is_dir() {
+      zip.add_directory(name.clone(), options)?;
+      add_directory(zip, &entry.path(), &format!("{}/", name))?;
+    } else {
+      zip.start_file(name, options)?;
+      zip.write_all(&fs::read(entry.path())?)?;
+    }
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn zips_a_channel_directory() {
+    let root = std::env::temp_dir().join(format!("mote-channel-{}", std::process::id()));
+    fs::create_dir_all(root.join("source")).unwrap();
+    fs::create_dir_all(root.join(".git")).unwrap();
+    fs::write(root.join("manifest"), "title=Test\n").unwrap();
+    fs::write(root.join("source/main.brs"), "sub Main()\nend sub\n").unwrap();
+    fs::write(root.join(".git/HEAD"), "ref: refs/heads/main\n").unwrap();
+
+    let archive = archive(&root).unwrap();
+    fs::remove_dir_all(&root).unwrap();
+
+    let zip = zip::ZipArchive::new(Cursor::new(archive)).unwrap();
+    let mut names: Vec<_> = zip.file_names().collect();
+    names.sort();
+
+    assert_eq!(names, vec!["manifest", "source/", "source/main.brs"]);
+  }
+
+  #[test]
+  fn refuses_a_directory_without_a_manifest() {
+    let root = std::env::temp_dir().join(format!("mote-empty-{}", std::process::id()));
+    fs::create_dir_all(&root).unwrap();
+
+    let error = archive(&root).unwrap_err();
+    fs::remove_dir_all(&root).unwrap();
+    assert!(error.to_string().contains("has no manifest"));
+  }
+}
@@ -0,0 +1,99 @@
+use std::{net::SocketAddr, time::Duration};
+use mote::devices::{self, Capabilities, Device, DeviceInfo, DeviceType};
+use crate::ui::info_tree::info_tree;
+use super::target;
+
+/// Everything known about a device, as printed by `--json`
+#[derive(Debug, serde::Serialize)]
+struct DeviceReport<'a> {
+  device_type: DeviceType,
+  location: Option<SocketAddr>,
+  info: &'a DeviceInfo,
+  capabilities: Capabilities
+}
+
+impl<'a> DeviceReport<'a> {
+  fn new(device: &'a Device) -> Self {
+    DeviceReport { device_type: device.device_type(), location: device.location(), info: device.info(), capabilities: device.capabilities() }
+  }
+}
+
+/// Prints every device found within `search`, as a table or as a JSON array.
+pub async fn list(search: Duration, json: bool) -> anyhow::Result<()> {
+  let devices = devices::discover_for(search).await;
+
+  if json {
+    let reports: Vec<_> = devices.iter().map(DeviceReport::new).collect();
+    println!("{}", serde_json::to_string_pretty(&reports)?);
+    return Ok(())
+  }
+
+  let rows: Vec<[String; 6]> = devices
+    .iter()
+    .map(|device| {
+      let info = device.info();
+      [
+        info.name.clone(),
+        device.device_type().to_string(),
+        device.address(),
+        info.product.model.name.clone(),
+        info.product.serial_number.clone(),
+        info.system.software_version.clone().unwrap_or_default()
+      ]
+    })
+    .collect();
+
+  print!("{}", table(["NAME", "PLATFORM", "ADDRESS", "MODEL", "SERIAL", "SOFTWARE"], rows));
+  Ok(())
+}
+
+/// Prints everything the device `selector` names reports about itself, as the info tree or as JSON.
+pub async fn info(selector: &str, json: bool) -> anyhow::Result<()> {
+  let device = target::find(Some(selector), |_| true).await?;
+
+  if json {
+    println!("{}", serde_json::to_string_pretty(&DeviceReport::new(&device))?);
+    return Ok(())
+  }
+
+  for line in info_tree(&device) {
+    println!("{}", line.0.iter().map(|span| span.content.as_ref()).collect::<String>());
+  }
+
+  Ok(())
+}
+
+/// Lines up `rows` under `header` in columns two spaces apart.
+fn table<const N: usize>(header: [&str; N], rows: Vec<[String; N]>) -> String {
+  let mut widths = header.map(|title| title.chars().count());
+  for row in &rows {
+    for (width, cell) in widths.iter_mut().zip(row) { *width = (*width).max(cell.chars().count()); }
+  }
+
+  std::iter::once(header.map(str::to_owned))
+    .chain(rows)
+    .map(|row| {
+      let cells: Vec<String> = row.iter().zip(widths).map(|(cell, width)| format!("{:<width$}", cell, width = width)).collect();
+      format!("{}\n", cells.join("  ").trim_end())
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn lines_up_columns() {
+    let rows = vec![
+      ["Living Room".to_owned(), "192.168.1.40".to_owned()],
+      ["Den".to_owned(), "192.168.1.7".to_owned()]
+    ];
+
+    assert_eq!(table(["NAME", "ADDRESS"], rows), "\
+NAME         ADDRESS
+Living Room  192.168.1.40
+Den          192.168.1.7
+");
+  }
+}
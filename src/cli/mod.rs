@@ -0,0 +1,155 @@
+use std::{path::PathBuf, time::Duration};
+use clap::{Args, Parser, Subcommand};
+use mote::{config::Config, devices};
+use crate::{harness::Recorder, ui};
+
+mod dev;
+mod inventory;
+mod replay;
+mod target;
+
+/// Control Roku TVs from your terminal. With no command, opens the remote.
+#[derive(Debug, Parser)]
+#[command(name = "mote", bin_name = "cargo mote", version, about)]
+pub struct Cli {
+  #[command(subcommand)]
+  pub command: Option<Command>
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+  /// List the devices on the network
+  List {
+    /// Print every device's full info as JSON instead of a table
+    #[arg(long)]
+    json: bool,
+
+    /// Seconds to search for
+    #[arg(long, default_value_t = 5)]
+    timeout: u64
+  },
+
+  /// Show everything a device reports about itself
+  Info {
+    /// Device to describe, by alias, name, serial number or IP address
+    device: String,
+
+    /// Print as JSON
+    #[arg(long)]
+    json: bool
+  },
+
+  /// Manage sideloaded channels on a Roku in developer mode
+  Dev(DevArgs),
+
+  /// Open the remote, recording the inputs sent and the app states they lead to
+  Record {
+    /// Session file to write
+    path: PathBuf
+  },
+
+  /// Replay a recorded session, checking the device reaches the same app states
+  Replay(ReplayArgs)
+}
+
+#[derive(Debug, Args)]
+pub struct ReplayArgs {
+  /// Session file written by `record`
+  path: PathBuf,
+
+  /// Device to replay against, by alias, name, serial number or IP address. Defaults to the one recorded.
+  #[arg(short, long)]
+  device: Option<String>,
+
+  /// Write the results as JUnit XML to this file
+  #[arg(long)]
+  junit: Option<PathBuf>,
+
+  /// Seconds a recorded state may take to be reached
+  #[arg(long, default_value_t = 10.0)]
+  wait: f64,
+
+  /// Seconds the playback position may be off by
+  #[arg(long, default_value_t = 5.0)]
+  position_tolerance: f64
+}
+
+#[derive(Debug, Args)]
+pub struct DevArgs {
+  /// Device to use, by alias, name, serial number or IP address. Defaults to the first Roku
+  /// found with developer mode enabled.
+  #[arg(short, long, global = true)]
+  device: Option<String>,
+
+  #[command(subcommand)]
+  command: DevCommand
+}
+
+#[derive(Debug, Subcommand)]
+enum DevCommand {
+  /// Sideload a channel, replacing the one sideloaded before
+  Install {
+    /// A zipped channel, or a channel's directory (the one holding `manifest`) to zip
+    path: PathBuf
+  },
+
+  /// Remove the sideloaded channel
+  Delete,
+
+  /// Print the BrightScript debug console's output with timestamps, until it hangs up
+  Logs {
+    /// Only print lines containing this, ignoring case
+    #[arg(short, long)]
+    filter: Option<String>,
+
+    /// Also append the printed lines to this file
+    #[arg(short, long)]
+    output: Option<PathBuf>
+  },
+
+  /// Sign the sideloaded channel into a `.pkg` for the channel store
+  Package {
+    /// Name and version to record in the package, e.g. `mychannel/1.0`
+    name: String,
+
+    /// Where to save the package, `<name>-<version>.pkg` by default
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Password of the device's signing key, overriding `signing_password` under [developer]
+    #[arg(long)]
+    signing_password: Option<String>
+  }
+}
+
+impl Cli {
+  /// Parses the command line, whether run as `cargo mote` (cargo passes `mote` along) or as `cargo-mote`.
+  pub fn parse_args() -> Self {
+    let mut args: Vec<_> = std::env::args_os().collect();
+    if args.get(1).is_some_and(|arg| arg == "mote") { args.remove(1); }
+
+    Cli::parse_from(args)
+  }
+}
+
+/// Runs a command to completion, outside the terminal UI.
+pub async fn run(command: Command, config: Config) -> anyhow::Result<()> {
+  match command {
+    Command::List { json, timeout } => inventory::list(Duration::from_secs(timeout), json).await,
+    Command::Info { device, json } => inventory::info(&device, json).await,
+    Command::Dev(args) => dev::run(args, &config).await,
+    Command::Record { path } => {
+      let recorder = Recorder::create(&path)?;
+
+      // drop returns terminal to normal mode
+      ui::UI::new()
+        .record(recorder)
+        .listen(
+          devices::discover()
+        ).await;
+
+      Ok(())
+    },
+    Command::Replay(args) => replay::run(args).await
+  }
+}
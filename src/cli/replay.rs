@@ -0,0 +1,36 @@
+use std::{fs, time::Duration};
+use anyhow::{bail, Context};
+use crate::harness::{junit, replay, Session, Tolerances};
+use super::{target, ReplayArgs};
+
+pub async fn run(args: ReplayArgs) -> anyhow::Result<()> {
+  let session = Session::read(&args.path)?;
+  let selector = args.device.as_deref().unwrap_or(&session.header.serial_number);
+  let device = target::find(Some(selector), |_| true).await?;
+
+  let tolerances = Tolerances {
+    wait: Duration::from_secs_f64(args.wait),
+    position: Duration::from_secs_f64(args.position_tolerance)
+  };
+
+  eprintln!("Replaying {} against {}", args.path.display(), device.info().name);
+  let cases = replay(&session, &device, tolerances).await;
+
+  for case in &cases {
+    match &case.failure {
+      Some(failure) => eprintln!("FAIL {}: {}", case.name, failure),
+      None => eprintln!("ok   {}", case.name)
+    }
+  }
+
+  if let Some(path) = &args.junit {
+    let suite = args.path.file_name().unwrap_or_default().to_string_lossy();
+    fs::write(path, junit(&suite, &cases)).with_context(|| format!("Failed to write {}", path.display()))?;
+  }
+
+  let failures = cases.iter().filter(|case| case.failure.is_some()).count();
+  if failures > 0 { bail!("{} of {} steps failed", failures, cases.len()) }
+
+  eprintln!("All {} steps passed", cases.len());
+  Ok(())
+}
@@ -0,0 +1,18 @@
+use std::time::Duration;
+use anyhow::anyhow;
+use mote::devices::{self, Device};
+
+/// How long to search for the device a command names before giving up
+const SEARCH_TIME: Duration = Duration::from_secs(10);
+
+/// Finds the device `selector` names (see `devices::find_device`), or the first device
+/// `accept`s if none is named.
+pub async fn find(selector: Option<&str>, accept: impl Fn(&Device) -> bool) -> anyhow::Result<Device> {
+  if let Some(selector) = selector { return Ok(devices::find_device(selector, SEARCH_TIME).await?) }
+
+  devices::discover_until(SEARCH_TIME, |found| found.last().is_some_and(&accept))
+    .await
+    .pop()
+    .filter(&accept)
+    .ok_or_else(|| anyhow!("No suitable device was found within {}s", SEARCH_TIME.as_secs()))
+}
@@ -1,8 +0,0 @@
-use super::Model;
-
-#[derive(Debug, Clone)]
-pub struct Product {
-  pub vendor: String,
-  pub model: Model,
-  pub serial_number: String
-}
\ No newline at end of file
@@ -1,6 +0,0 @@
-use super::Uptime;
-
-#[derive(Debug, Clone)]
-pub struct System {
-  pub uptime: Option<Uptime>
-}
\ No newline at end of file
@@ -1,2 +0,0 @@
-#[derive(Debug, Clone)]
-pub enum DeviceType { Roku }
\ No newline at end of file
@@ -1,7 +0,0 @@
-mod roku_device_info;
-mod roku_key;
-mod roku_input;
-
-pub use roku_device_info::*;
-pub use roku_key::*;
-pub use roku_input::*;
\ No newline at end of file
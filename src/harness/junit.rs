@@ -0,0 +1,55 @@
+use std::time::Duration;
+use super::TestCase;
+
+/// A JUnit XML report of one test suite, the format CI servers read test results in.
+pub fn junit(suite: &str, cases: &[TestCase]) -> String {
+  let failures = cases.iter().filter(|case| case.failure.is_some()).count();
+  let time: Duration = cases.iter().map(|case| case.time).sum();
+
+  let mut xml = format!(
+    "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" time=\"{:.3}\">\n",
+    escape(suite), cases.len(), failures, time.as_secs_f64()
+  );
+
+  for case in cases {
+    let opening = format!("  <testcase classname=\"mote.replay\" name=\"{}\" time=\"{:.3}\"", escape(&case.name), case.time.as_secs_f64());
+
+    match &case.failure {
+      Some(failure) => xml += &format!("{}>\n    <failure message=\"{}\"/>\n  </testcase>\n", opening, escape(failure)),
+      None => xml += &format!("{}/>\n", opening)
+    }
+  }
+
+  xml + "</testsuite>\n"
+}
+
+fn escape(text: &str) -> String {
+  text
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+    .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn reports_failures() {
+    let cases = vec![
+      TestCase { name: "0.5s: send KeyPress(Ok)".into(), time: Duration::from_millis(20), failure: None },
+      TestCase { name: "2.0s: reach Netflix".into(), time: Duration::from_millis(1500), failure: Some("Found \"Roku\" <home>".into()) }
+    ];
+
+    assert_eq!(junit("living-room.jsonl", &cases), r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuite name="living-room.jsonl" tests="2" failures="1" time="1.520">
+  <testcase classname="mote.replay" name="0.5s: send KeyPress(Ok)" time="0.020"/>
+  <testcase classname="mote.replay" name="2.0s: reach Netflix" time="1.500">
+    <failure message="Found &quot;Roku&quot; &lt;home&gt;"/>
+  </testcase>
+</testsuite>
+"#);
+  }
+}
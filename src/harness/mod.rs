@@ -0,0 +1,12 @@
+//! Turns manual sessions into regression tests: a session is recorded from the remote,
+//! then replayed against a device, checking it reaches the same app states along the way.
+
+mod junit;
+mod recorder;
+mod replay;
+mod session;
+
+pub use junit::junit;
+pub use recorder::Recorder;
+pub use replay::{replay, TestCase, Tolerances};
+pub use session::{Session, Step};
@@ -0,0 +1,73 @@
+use std::{path::Path, time::{Duration, Instant}};
+use mote::devices::{AppState, DeviceInfo, DeviceInput};
+use super::session::{Entry, Header, SessionWriter, Step};
+
+/// How often the recorded device's app state is sampled
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Records a session from the remote as it is used. The first input decides which device
+/// is being recorded; from then on its app state is sampled every `SAMPLE_INTERVAL`.
+#[derive(Debug)]
+pub struct Recorder {
+  writer: SessionWriter,
+  started: Instant,
+
+  /// Serial number of the device being recorded, once an input has been sent
+  device: Option<String>,
+
+  /// When the sample in flight (or the last one) was asked for
+  sampled_at: Option<Instant>,
+  sampling: bool,
+
+  /// Last state written, so a device sitting still is not recorded over and over
+  last_state: Option<AppState>
+}
+
+impl Recorder {
+  pub fn create(path: &Path) -> anyhow::Result<Self> {
+    Ok(Recorder { writer: SessionWriter::create(path)?, started: Instant::now(), device: None, sampled_at: None, sampling: false, last_state: None })
+  }
+
+  /// Records an input sent to `device`. False if it went to a device other than the one being recorded, and was not recorded.
+  pub fn input(&mut self, device: &DeviceInfo, input: &DeviceInput) -> anyhow::Result<bool> {
+    let serial_number = &device.product.serial_number;
+
+    match &self.device {
+      Some(recorded) if recorded != serial_number => return Ok(false),
+      Some(_) => (),
+      None => {
+        self.writer.write(&Header { device: device.name.clone(), serial_number: serial_number.clone() })?;
+        self.device = Some(serial_number.clone());
+      }
+    }
+
+    self.write(Instant::now(), Step::Input(input.clone()))?;
+    Ok(true)
+  }
+
+  /// The device to sample now, if a sample is due. A sample is then in flight until `sampled`.
+  pub fn sample_due(&mut self) -> Option<String> {
+    let device = self.device.clone()?;
+    if self.sampling || self.sampled_at.is_some_and(|at| at.elapsed() < SAMPLE_INTERVAL) { return None }
+
+    self.sampling = true;
+    self.sampled_at = Some(Instant::now());
+    Some(device)
+  }
+
+  /// Records a sample's result, unless the device has not changed since the last one. `None` if it could not be taken.
+  pub fn sampled(&mut self, state: Option<AppState>) -> anyhow::Result<()> {
+    self.sampling = false;
+
+    let (Some(state), Some(at)) = (state, self.sampled_at) else { return Ok(()) };
+    if self.last_state.as_ref() == Some(&state) { return Ok(()) }
+
+    self.write(at, Step::State(state.clone()))?;
+    self.last_state = Some(state);
+    Ok(())
+  }
+
+  fn write(&mut self, at: Instant, step: Step) -> anyhow::Result<()> {
+    self.writer.write(&Entry { at: at.duration_since(self.started).as_secs_f64(), step })
+  }
+}
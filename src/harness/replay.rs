@@ -0,0 +1,131 @@
+use std::time::{Duration, Instant};
+use tokio::time::{sleep, sleep_until};
+use mote::devices::{AppState, Device};
+use super::{Session, Step};
+
+/// How often the device is asked for its state while waiting for it to match
+const POLL_RATE: Duration = Duration::from_millis(250);
+
+/// How far a replayed session may stray from the recording and still pass
+#[derive(Debug, Clone, Copy)]
+pub struct Tolerances {
+  /// How long after its recorded time a state may take to be reached
+  pub wait: Duration,
+
+  /// How far the playback position may be from the recorded one
+  pub position: Duration
+}
+
+/// The outcome of one step of a replay
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestCase {
+  pub name: String,
+  pub time: Duration,
+
+  /// Why the step failed, if it did
+  pub failure: Option<String>
+}
+
+/// Replays a session against `device`: each input is sent at the time it was recorded,
+/// and each recorded state is waited for, from its recorded time for up to `tolerances.wait`.
+pub async fn replay(session: &Session, device: &Device, tolerances: Tolerances) -> Vec<TestCase> {
+  let started = tokio::time::Instant::now();
+  let mut cases = vec![];
+
+  for entry in &session.entries {
+    sleep_until(started + Duration::from_secs_f64(entry.at)).await;
+    let step_started = Instant::now();
+
+    let (name, failure) = match &entry.step {
+      Step::Input(input) => (
+        format!("{:.1}s: send {:?}", entry.at, input),
+        device.send_input(input.clone()).await.err().map(|e| format!("{:#}", e))
+      ),
+      Step::State(expected) => (
+        format!("{:.1}s: reach {}", entry.at, expected),
+        wait_for(device, expected, tolerances).await.err()
+      )
+    };
+
+    cases.push(TestCase { name, time: step_started.elapsed(), failure });
+  }
+
+  cases
+}
+
+/// Polls the device until it is in the `expected` state, or describes what it was in instead.
+async fn wait_for(device: &Device, expected: &AppState, tolerances: Tolerances) -> Result<(), String> {
+  let deadline = Instant::now() + tolerances.wait;
+
+  loop {
+    let found = match device.app_state().await {
+      Ok(actual) if reached(&actual, expected, tolerances.position) => return Ok(()),
+      Ok(actual) => format!("found {}", actual),
+      Err(e) => format!("could not query the device: {:#}", e)
+    };
+
+    if Instant::now() >= deadline {
+      return Err(format!("Expected {} within {:.1}s, {}", expected, tolerances.wait.as_secs_f64(), found))
+    }
+
+    sleep(POLL_RATE).await;
+  }
+}
+
+/// Whether `actual` is the `expected` state: the same app, its player in the same state,
+/// and playback within `position_tolerance` of where it was.
+fn reached(actual: &AppState, expected: &AppState, position_tolerance: Duration) -> bool {
+  // the home screen has no id, only a name
+  let same_app = match (&actual.app_id, &expected.app_id) {
+    (None, None) => actual.app_name == expected.app_name,
+    (actual, expected) => actual == expected
+  };
+
+  let same_media = match (&actual.media, &expected.media) {
+    (None, None) => true,
+    (Some(actual), Some(expected)) => {
+      let near = match (actual.position_ms, expected.position_ms) {
+        (Some(actual), Some(expected)) => actual.abs_diff(expected) <= position_tolerance.as_millis() as u64,
+        _ => true
+      };
+
+      actual.state == expected.state && near
+    },
+    _ => false
+  };
+
+  same_app && same_media
+}
+
+#[cfg(test)]
+mod tests {
+  use mote::devices::MediaState;
+  use super::*;
+
+  fn playing(state: &str, position_ms: u64) -> AppState {
+    AppState {
+      app_id: Some("12".into()),
+      app_name: "Netflix".into(),
+      media: Some(MediaState { state: state.into(), position_ms: Some(position_ms), duration_ms: Some(3012000) })
+    }
+  }
+
+  #[test]
+  fn tolerates_drift_in_playback_position() {
+    let tolerance = Duration::from_secs(5);
+
+    assert!(reached(&playing("play", 84000), &playing("play", 80000), tolerance));
+    assert!(!reached(&playing("play", 86000), &playing("play", 80000), tolerance));
+    assert!(!reached(&playing("pause", 80000), &playing("play", 80000), tolerance));
+  }
+
+  #[test]
+  fn tells_apps_apart() {
+    let home = AppState { app_id: None, app_name: "Roku".into(), media: None };
+    let youtube = AppState { app_id: Some("837".into()), app_name: "YouTube".into(), media: None };
+
+    assert!(reached(&home, &home, Duration::ZERO));
+    assert!(!reached(&youtube, &home, Duration::ZERO));
+    assert!(!reached(&playing("play", 0), &AppState { media: None, ..playing("play", 0) }, Duration::ZERO));
+  }
+}
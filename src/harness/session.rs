@@ -0,0 +1,109 @@
+use std::{fs::{self, File}, io::{BufWriter, Write}, path::Path};
+use anyhow::{anyhow, Context};
+use mote::devices::{AppState, DeviceInput};
+
+/// Which device a session was recorded against, the first line of a session file
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Header {
+  pub device: String,
+  pub serial_number: String
+}
+
+/// Something that happened during a session, and when
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Entry {
+  /// Seconds since recording started
+  pub at: f64,
+
+  #[serde(flatten)]
+  pub step: Step
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Step {
+  /// The user sent an input
+  Input(DeviceInput),
+
+  /// The device was sampled in this state
+  State(AppState)
+}
+
+/// A recorded session. Stored as JSON lines, the header and then one entry per line,
+/// so a recording cut short is still readable up to where it stopped.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Session {
+  pub header: Header,
+  pub entries: Vec<Entry>
+}
+
+impl Session {
+  pub fn read(path: &Path) -> anyhow::Result<Self> {
+    let contents = fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut lines = contents.lines().enumerate().filter(|(_, line)| !line.trim().is_empty());
+
+    let (_, header) = lines.next().ok_or_else(|| anyhow!("{} is empty", path.display()))?;
+    let header: Header = serde_json::from_str(header).with_context(|| format!("Invalid session header in {}", path.display()))?;
+
+    let entries: Vec<Entry> = lines
+      .map(|(number, line)| serde_json::from_str(line).with_context(|| format!("Invalid entry on line {} of {}", number + 1, path.display())))
+      .collect::<anyhow::Result<_>>()?;
+
+    Ok(Session { header, entries })
+  }
+}
+
+/// Writes a session file as it is recorded, a line at a time.
+#[derive(Debug)]
+pub struct SessionWriter {
+  file: BufWriter<File>
+}
+
+impl SessionWriter {
+  /// Creates (or empties) the session file.
+  pub fn create(path: &Path) -> anyhow::Result<Self> {
+    let file = File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+    Ok(SessionWriter { file: BufWriter::new(file) })
+  }
+
+  /// Appends the header or an entry, flushing so nothing is lost if mote is killed.
+  pub fn write(&mut self, line: &impl serde::Serialize) -> anyhow::Result<()> {
+    serde_json::to_writer(&mut self.file, line)?;
+    writeln!(self.file)?;
+    Ok(self.file.flush()?)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use mote::devices::{Key, MediaState};
+  use super::*;
+
+  #[test]
+  fn round_trips_through_a_file() {
+    let path = std::env::temp_dir().join(format!("mote-session-{}.jsonl", std::process::id()));
+    let session = Session {
+      header: Header { device: "Living Room".into(), serial_number: "X00400ABCDEF".into() },
+      entries: vec![
+        Entry { at: 0.5, step: Step::Input(DeviceInput::Launch("12".into())) },
+        Entry { at: 1.25, step: Step::Input(Key::Ok.into()) },
+        Entry { at: 2.0, step: Step::State(AppState {
+          app_id: Some("12".into()),
+          app_name: "Netflix".into(),
+          media: Some(MediaState { state: "play".into(), position_ms: Some(1500), duration_ms: None })
+        }) }
+      ]
+    };
+
+    let mut writer = SessionWriter::create(&path).unwrap();
+    writer.write(&session.header).unwrap();
+    for entry in &session.entries { writer.write(entry).unwrap(); }
+
+    let contents = fs::read_to_string(&path).unwrap();
+    let read = Session::read(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(contents.lines().nth(2), Some(r#"{"at":1.25,"input":{"KeyPress":"Ok"}}"#));
+    assert_eq!(read, session);
+  }
+}
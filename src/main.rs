@@ -1,14 +1,32 @@
-mod devices;
+use mote::config::Config;
+
+mod cli;
+mod harness;
 mod ui;
 
 #[tokio::main]
 async fn main() {
-  // drop returns terminal to normal mode
-  ui::UI::new()
-    .listen(
-      devices::discover()
-    ).await;
-
-  // forcibly exit process whenever UI task finishes
-  std::process::exit(0);
+  let cli = cli::Cli::parse_args();
+
+  // report a broken config file before the terminal is taken over
+  let config = match Config::load() {
+    Ok(config) => config,
+    Err(e) => {
+      eprintln!("{:#}", e);
+      std::process::exit(1);
+    }
+  };
+
+  match cli.command {
+    Some(command) => if let Err(e) = cli::run(command, config).await {
+      eprintln!("{:#}", e);
+      std::process::exit(1);
+    },
+
+    // drop returns terminal to normal mode
+    None => ui::UI::new()
+      .listen(
+        mote::discover()
+      ).await
+  }
 }
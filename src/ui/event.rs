@@ -0,0 +1,137 @@
+use std::time::Duration;
+use tokio::{signal::{ctrl_c, unix::{signal, SignalKind}}, spawn, sync::mpsc::{UnboundedReceiver, UnboundedSender}, task::JoinHandle, time::interval};
+use mote::devices::{App, AppState, Channel, Device, Key, LogLine};
+use super::{graphics::Image, selection::Identified, user_input::UserInput};
+
+/// How often the UI is woken up to redraw time-based state
+pub const TICK_RATE: Duration = Duration::from_millis(100);
+
+/// How often each known device is asked for fresh state
+pub const POLL_RATE: Duration = Duration::from_secs(5);
+
+/// Consecutive failed polls before a device is considered gone
+const POLL_FAILURE_LIMIT: usize = 3;
+
+/// Everything the UI reacts to, funneled through a single channel.
+pub enum Event {
+  /// The user pressed a key or clicked
+  Input(UserInput),
+
+  /// Discovery found a device we had not seen before
+  DeviceAdded(Device),
+
+  /// A device stopped answering state polls, identified by its serial number
+  DeviceRemoved(String),
+
+  /// A device answered a state poll, carrying fresh device info
+  DevicePolled(Device),
+
+  /// A keypress request finished, successfully or not, for the device with this serial number
+  KeySent(String, Key, mote::Result<()>),
+
+  /// A device refused control from this computer (a Roku in Limited mode, say), for the device with this serial number
+  ControlRefused(String),
+
+  /// A device's channel list arrived, with the channel it is tuned to, for the device with this serial number
+  ChannelsLoaded(String, anyhow::Result<(Vec<Channel>, Option<Channel>)>),
+
+  /// A device's installed apps arrived, for the device with this serial number
+  AppsLoaded(String, anyhow::Result<Vec<App>>),
+
+  /// An app icon arrived, for the device with this serial number and the app with this id
+  IconLoaded(String, String, anyhow::Result<Image>),
+
+  /// The developer panel's capture of a device's screen finished, for the device with this serial number
+  ScreenCaptured(String, anyhow::Result<Image>),
+
+  /// A line arrived on the log pane's debug console, for the device with this serial number
+  LogLine(String, LogLine),
+
+  /// The log pane's debug console hung up, or could not be reached, for the device with this serial number
+  LogClosed(String, anyhow::Result<()>),
+
+  /// The session recorder's sample of the recorded device's app state finished
+  StateSampled(anyhow::Result<AppState>),
+
+  /// Periodic wake-up so time-based state (flashes, uptime) stays current
+  Tick,
+
+  /// A background task failed in a way the user should hear about
+  Error(String),
+
+  /// The process was asked to stop (SIGINT, SIGTERM)
+  Shutdown
+}
+
+/// Forwards discovered devices into the event channel.
+pub fn forward_discovery(mut rx: UnboundedReceiver<Device>, tx: UnboundedSender<Event>) -> JoinHandle<()> {
+  spawn(async move {
+    while let Some(device) = rx.recv().await {
+      if tx.send(Event::DeviceAdded(device)).is_err() { break }
+    }
+  })
+}
+
+/// Emits `Event::Tick` at `TICK_RATE` until the UI goes away.
+pub fn tick(tx: UnboundedSender<Event>) -> JoinHandle<()> {
+  spawn(async move {
+    let mut ticker = interval(TICK_RATE);
+    loop {
+      ticker.tick().await;
+      if tx.send(Event::Tick).is_err() { break }
+    }
+  })
+}
+
+/// Periodically re-queries a device. Reports it removed once it stops answering,
+/// and keeps polling so it can come back if it returns.
+pub fn poll(device: Device, tx: UnboundedSender<Event>) -> JoinHandle<()> {
+  spawn(async move {
+    let mut ticker = interval(POLL_RATE);
+    let mut failures = 0;
+
+    // first tick completes immediately, and we already have fresh info
+    ticker.tick().await;
+
+    loop {
+      ticker.tick().await;
+
+      let event = match device.query_state().await {
+        Ok(device) => {
+          failures = 0;
+          Event::DevicePolled(device)
+        },
+        // a device refusing queries is still there, only locked down
+        Err(mote::Error::Permission(_)) => {
+          failures = 0;
+          Event::ControlRefused(device.id())
+        },
+        Err(e) => {
+          failures += 1;
+          if failures != POLL_FAILURE_LIMIT { continue }
+
+          let message = format!("{} stopped responding: {}", device.info().name, super::explain(&e));
+          if tx.send(Event::Error(message)).is_err() { break }
+          Event::DeviceRemoved(device.id())
+        }
+      };
+
+      if tx.send(event).is_err() { break }
+    }
+  })
+}
+
+/// Turns SIGINT and SIGTERM into `Event::Shutdown`, so the terminal is restored before exiting.
+/// (In raw mode Ctrl-C arrives as a key instead, but the signal can still come from elsewhere.)
+pub fn signals(tx: UnboundedSender<Event>) -> JoinHandle<()> {
+  spawn(async move {
+    let mut terminate = signal(SignalKind::terminate()).expect("Failed to listen for SIGTERM");
+
+    tokio::select! {
+      _ = ctrl_c() => (),
+      _ = terminate.recv() => ()
+    }
+
+    let _ = tx.send(Event::Shutdown);
+  })
+}
@@ -0,0 +1,36 @@
+use tui::{style::{Color, Style}, text::{Span, Spans}};
+use super::Image;
+
+/// Draws an image with `▀` characters, two pixels per cell: the top one in the foreground
+/// color and the bottom one in the background. Works in any terminal with true color.
+pub fn half_blocks(image: &Image) -> Vec<Spans<'static>> {
+  let rgb = |[r, g, b]: [u8; 3]| Color::Rgb(r, g, b);
+
+  (0..image.height)
+    .step_by(2)
+    .map(|y| Spans::from(
+      (0..image.width)
+        .map(|x| {
+          let bottom = if y + 1 < image.height { image.pixel(x, y + 1) } else { [0; 3] };
+          Span::styled("▀", Style::default().fg(rgb(image.pixel(x, y))).bg(rgb(bottom)))
+        })
+        .collect::<Vec<_>>()
+    ))
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn packs_two_rows_per_line() {
+    let image = Image { width: 2, height: 3, pixels: vec![[1; 3], [2; 3], [3; 3], [4; 3], [5; 3], [6; 3]] };
+    let lines = half_blocks(&image);
+
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0].0.len(), 2);
+    assert_eq!(lines[0].0[1].style, Style::default().fg(Color::Rgb(2, 2, 2)).bg(Color::Rgb(4, 4, 4)));
+    assert_eq!(lines[1].0[0].style, Style::default().fg(Color::Rgb(5, 5, 5)).bg(Color::Rgb(0, 0, 0)));
+  }
+}
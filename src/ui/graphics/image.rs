@@ -0,0 +1,132 @@
+/// A decoded picture, as opaque 8-bit RGB pixels in rows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+  pub width: u32,
+  pub height: u32,
+  pub pixels: Vec<[u8; 3]>
+}
+
+impl Image {
+  /// Decodes a PNG or JPEG, told apart by their signatures.
+  pub fn decode(bytes: &[u8]) -> anyhow::Result<Self> {
+    match bytes {
+      [0x89, b'P', b'N', b'G', ..] => Self::decode_png(bytes),
+      [0xFF, 0xD8, ..] => Self::decode_jpeg(bytes),
+      _ => anyhow::bail!("Unrecognized image format")
+    }
+  }
+
+  /// Decodes a PNG, flattening any transparency onto black (the UI's background).
+  pub fn decode_png(bytes: &[u8]) -> anyhow::Result<Self> {
+    let mut decoder = png::Decoder::new(bytes);
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+
+    let mut reader = decoder.read_info()?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let frame = reader.next_frame(&mut buffer)?;
+    let buffer = &buffer[..frame.buffer_size()];
+
+    let over_black = |value: u8, alpha: u8| (value as u16 * alpha as u16 / 255) as u8;
+    let pixels = match frame.color_type {
+      png::ColorType::Rgb => buffer.chunks_exact(3).map(|p| [p[0], p[1], p[2]]).collect(),
+      png::ColorType::Rgba => buffer.chunks_exact(4).map(|p| [over_black(p[0], p[3]), over_black(p[1], p[3]), over_black(p[2], p[3])]).collect(),
+      png::ColorType::Grayscale => buffer.iter().map(|&v| [v, v, v]).collect(),
+      png::ColorType::GrayscaleAlpha => buffer.chunks_exact(2).map(|p| [over_black(p[0], p[1]); 3]).collect(),
+      // expanded away by `normalize_to_color8`
+      png::ColorType::Indexed => anyhow::bail!("Palette PNG was not expanded")
+    };
+
+    Ok(Image { width: frame.width, height: frame.height, pixels })
+  }
+
+  /// Decodes a baseline or progressive JPEG, as device screenshots are.
+  pub fn decode_jpeg(bytes: &[u8]) -> anyhow::Result<Self> {
+    let mut decoder = jpeg_decoder::Decoder::new(bytes);
+    let buffer = decoder.decode()?;
+    let info = decoder.info().ok_or_else(|| anyhow::anyhow!("JPEG has no image header"))?;
+
+    let pixels = match info.pixel_format {
+      jpeg_decoder::PixelFormat::RGB24 => buffer.chunks_exact(3).map(|p| [p[0], p[1], p[2]]).collect(),
+      jpeg_decoder::PixelFormat::L8 => buffer.iter().map(|&v| [v, v, v]).collect(),
+      format => anyhow::bail!("Unsupported JPEG pixel format {:?}", format)
+    };
+
+    Ok(Image { width: info.width as u32, height: info.height as u32, pixels })
+  }
+
+  /// Encodes the image as an RGB PNG, for protocols that take image files.
+  pub fn encode_png(&self) -> anyhow::Result<Vec<u8>> {
+    let mut bytes = vec![];
+
+    let mut encoder = png::Encoder::new(&mut bytes, self.width, self.height);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.write_header()?.write_image_data(&self.pixels.concat())?;
+
+    Ok(bytes)
+  }
+
+  /// Scales the image to fit exactly `width` by `height`, keeping its proportions and
+  /// centering it on black. The result always covers the whole box, so drawing a new
+  /// picture over an old one never leaves part of the old one showing.
+  pub fn fit(&self, width: u32, height: u32) -> Image {
+    let mut pixels = vec![[0; 3]; (width * height) as usize];
+    if self.width == 0 || self.height == 0 || width == 0 || height == 0 {
+      return Image { width, height, pixels }
+    }
+
+    let scale = f64::min(width as f64 / self.width as f64, height as f64 / self.height as f64);
+    let scaled_width = ((self.width as f64 * scale).round() as u32).clamp(1, width);
+    let scaled_height = ((self.height as f64 * scale).round() as u32).clamp(1, height);
+    let (left, top) = ((width - scaled_width) / 2, (height - scaled_height) / 2);
+
+    // nearest neighbour, sampled at each target pixel's center
+    for y in 0..scaled_height {
+      let source_y = (((y as f64 + 0.5) / scale) as u32).min(self.height - 1);
+      for x in 0..scaled_width {
+        let source_x = (((x as f64 + 0.5) / scale) as u32).min(self.width - 1);
+        pixels[((top + y) * width + left + x) as usize] = self.pixels[(source_y * self.width + source_x) as usize];
+      }
+    }
+
+    Image { width, height, pixels }
+  }
+
+  pub fn pixel(&self, x: u32, y: u32) -> [u8; 3] {
+    self.pixels[(y * self.width + x) as usize]
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn round_trips_through_png() {
+    let image = Image { width: 2, height: 1, pixels: vec![[255, 0, 0], [0, 0, 255]] };
+    let decoded = Image::decode_png(&image.encode_png().unwrap()).unwrap();
+
+    assert_eq!(decoded, image);
+  }
+
+  #[test]
+  fn tells_formats_apart() {
+    let image = Image { width: 1, height: 1, pixels: vec![[0, 255, 0]] };
+
+    assert_eq!(Image::decode(&image.encode_png().unwrap()).unwrap(), image);
+    assert!(Image::decode(b"GIF89a").is_err());
+  }
+
+  #[test]
+  fn letterboxes_when_fitting() {
+    // a wide red image in a square box gets black bars above and below
+    let image = Image { width: 4, height: 2, pixels: vec![[255, 0, 0]; 8] };
+    let fitted = image.fit(4, 4);
+
+    assert_eq!((fitted.width, fitted.height), (4, 4));
+    assert_eq!(fitted.pixel(0, 0), [0, 0, 0]);
+    assert_eq!(fitted.pixel(0, 1), [255, 0, 0]);
+    assert_eq!(fitted.pixel(3, 2), [255, 0, 0]);
+    assert_eq!(fitted.pixel(3, 3), [0, 0, 0]);
+  }
+}
@@ -0,0 +1,147 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+mod half_blocks;
+mod image;
+mod sixel;
+
+pub use half_blocks::half_blocks;
+pub use image::Image;
+
+/// Largest base64 payload kitty accepts per escape sequence
+const KITTY_CHUNK: usize = 4096;
+
+/// Cell size to assume when the terminal does not report its pixel size
+const FALLBACK_CELL_PIXELS: (u32, u32) = (8, 16);
+
+/// How pictures are drawn in this terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+  /// Kitty graphics protocol (kitty, Ghostty)
+  Kitty,
+
+  /// iTerm2 inline images (iTerm2, WezTerm)
+  Iterm,
+
+  /// DEC sixel graphics (foot, mlterm, xterm built with sixel)
+  Sixel,
+
+  /// Colored `▀` characters, which any true color terminal can show
+  HalfBlocks
+}
+
+impl Protocol {
+  /// Picks the best protocol the terminal is known to support. `MOTE_GRAPHICS`
+  /// (`kitty`, `iterm`, `sixel` or `blocks`) overrides the guess.
+  pub fn detect() -> Self {
+    Self::from_env(|name| std::env::var(name).ok())
+  }
+
+  fn from_env(var: impl Fn(&str) -> Option<String>) -> Self {
+    match var("MOTE_GRAPHICS").map(|choice| choice.to_lowercase()).as_deref() {
+      Some("kitty") => return Protocol::Kitty,
+      Some("iterm") => return Protocol::Iterm,
+      Some("sixel") => return Protocol::Sixel,
+      Some("blocks") => return Protocol::HalfBlocks,
+      _ => ()
+    }
+
+    let term = var("TERM").unwrap_or_default();
+    let program = var("TERM_PROGRAM").unwrap_or_default();
+
+    // multiplexers swallow graphics escapes unless specially configured
+    if var("TMUX").is_some() || term.starts_with("screen") { return Protocol::HalfBlocks }
+
+    if var("KITTY_WINDOW_ID").is_some() || term == "xterm-kitty" || term == "xterm-ghostty" || program == "ghostty" {
+      Protocol::Kitty
+    } else if program == "iTerm.app" || program == "WezTerm" {
+      Protocol::Iterm
+    } else if term.contains("sixel") || term.starts_with("foot") || term.starts_with("mlterm") {
+      Protocol::Sixel
+    } else {
+      Protocol::HalfBlocks
+    }
+  }
+
+  /// Escape sequence drawing `image` over `columns` by `rows` cells from the cursor, for
+  /// the protocols that draw with escapes. The image should already be fitted to that box.
+  pub fn escape(&self, image: &Image, columns: u16, rows: u16) -> Option<String> {
+    match self {
+      Protocol::Kitty => {
+        let payload = STANDARD.encode(image.encode_png().ok()?);
+        let chunks: Vec<&[u8]> = payload.as_bytes().chunks(KITTY_CHUNK).collect();
+
+        Some(
+          chunks
+            .iter()
+            .enumerate()
+            .map(|(i, chunk)| {
+              let more = (i + 1 < chunks.len()) as u8;
+              let chunk = std::str::from_utf8(chunk).unwrap_or_default();
+              match i {
+                0 => format!("\x1b_Ga=T,f=100,c={},r={},C=1,q=2,m={};{}\x1b\\", columns, rows, more, chunk),
+                _ => format!("\x1b_Gm={};{}\x1b\\", more, chunk)
+              }
+            })
+            .collect()
+        )
+      },
+      Protocol::Iterm => {
+        let png = image.encode_png().ok()?;
+        Some(format!("\x1b]1337;File=inline=1;size={};width={};height={};preserveAspectRatio=0:{}\x07", png.len(), columns, rows, STANDARD.encode(png)))
+      },
+      Protocol::Sixel => Some(sixel::encode(image)),
+      Protocol::HalfBlocks => None
+    }
+  }
+
+  /// Escape sequence removing pictures drawn earlier, for protocols whose pictures
+  /// outlive the text drawn over them. The rest vanish once their cells are redrawn.
+  pub fn clear(&self) -> Option<&'static str> {
+    match self {
+      Protocol::Kitty => Some("\x1b_Ga=d,q=2\x1b\\"),
+      _ => None
+    }
+  }
+}
+
+/// Pixel size of one terminal cell.
+pub fn cell_pixels() -> (u32, u32) {
+  match (termion::terminal_size(), termion::terminal_size_pixels()) {
+    (Ok((columns, rows)), Ok((width, height))) if columns > 0 && rows > 0 && width > 0 && height > 0 => {
+      (width as u32 / columns as u32, height as u32 / rows as u32)
+    },
+    _ => FALLBACK_CELL_PIXELS
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::collections::HashMap;
+  use super::*;
+
+  fn detect(vars: &[(&str, &str)]) -> Protocol {
+    let vars: HashMap<&str, &str> = vars.iter().copied().collect();
+    Protocol::from_env(|name| vars.get(name).map(|value| value.to_string()))
+  }
+
+  #[test]
+  fn detects_terminals() {
+    assert_eq!(detect(&[("TERM", "xterm-kitty")]), Protocol::Kitty);
+    assert_eq!(detect(&[("TERM", "xterm-256color"), ("TERM_PROGRAM", "iTerm.app")]), Protocol::Iterm);
+    assert_eq!(detect(&[("TERM", "foot")]), Protocol::Sixel);
+    assert_eq!(detect(&[("TERM", "xterm-256color")]), Protocol::HalfBlocks);
+    assert_eq!(detect(&[("TERM", "xterm-kitty"), ("TMUX", "/tmp/tmux-1000/default,1,0")]), Protocol::HalfBlocks);
+    assert_eq!(detect(&[("TERM", "xterm-256color"), ("MOTE_GRAPHICS", "Sixel")]), Protocol::Sixel);
+  }
+
+  #[test]
+  fn chunks_kitty_payloads() {
+    let image = Image { width: 64, height: 64, pixels: (0..64 * 64).map(|i| [i as u8, (i / 64) as u8, 7]).collect() };
+    let escape = Protocol::Kitty.escape(&image, 8, 4).unwrap();
+    let parts: Vec<&str> = escape.split("\x1b\\").filter(|part| !part.is_empty()).collect();
+
+    assert!(parts.len() > 1);
+    assert!(parts[0].starts_with("\x1b_Ga=T,f=100,c=8,r=4,C=1,q=2,m=1;"));
+    assert!(parts.last().unwrap().starts_with("\x1b_Gm=0;"));
+  }
+}
@@ -0,0 +1,78 @@
+use std::{collections::BTreeMap, fmt::Write};
+use super::Image;
+
+/// Levels per channel of the fixed palette, a 6x6x6 color cube
+const LEVELS: u16 = 6;
+
+/// Encodes an image as DEC sixel graphics, quantized to a fixed 216-color palette.
+pub fn encode(image: &Image) -> String {
+  let mut out = format!("\x1bPq\"1;1;{};{}", image.width, image.height);
+
+  // the palette, in percent
+  for index in 0..LEVELS.pow(3) {
+    let (r, g, b) = (index / (LEVELS * LEVELS), index / LEVELS % LEVELS, index % LEVELS);
+    let percent = |level: u16| level * 100 / (LEVELS - 1);
+    let _ = write!(out, "#{};2;{};{};{}", index, percent(r), percent(g), percent(b));
+  }
+
+  // each band is six pixel rows, drawn once per color it uses
+  for top in (0..image.height).step_by(6) {
+    let mut colors: BTreeMap<u16, Vec<u8>> = BTreeMap::new();
+
+    for y in top..(top + 6).min(image.height) {
+      for x in 0..image.width {
+        colors.entry(palette_index(image.pixel(x, y))).or_insert_with(|| vec![0; image.width as usize])[x as usize] |= 1 << (y - top);
+      }
+    }
+
+    for (i, (color, columns)) in colors.iter().enumerate() {
+      // `$` returns to the start of the band for the next color
+      if i > 0 { out.push('$'); }
+      let _ = write!(out, "#{}", color);
+      run_length(&mut out, columns);
+    }
+
+    out.push('-');
+  }
+
+  out.push_str("\x1b\\");
+  out
+}
+
+/// Closest color in the palette
+fn palette_index([r, g, b]: [u8; 3]) -> u16 {
+  let level = |value: u8| (value as u16 * (LEVELS - 1) + 127) / 255;
+  level(r) * LEVELS * LEVELS + level(g) * LEVELS + level(b)
+}
+
+/// Writes one color's columns as sixel characters, collapsing repeats into `!<count><char>`.
+fn run_length(out: &mut String, columns: &[u8]) {
+  let mut i = 0;
+  while i < columns.len() {
+    let run = columns[i..].iter().take_while(|&&bits| bits == columns[i]).count();
+    let sixel = (63 + columns[i]) as char;
+
+    if run > 3 {
+      let _ = write!(out, "!{}{}", run, sixel);
+    } else {
+      (0..run).for_each(|_| out.push(sixel));
+    }
+
+    i += run;
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn encodes_bands_and_runs() {
+    // one full band of white over a partial band of black
+    let image = Image { width: 5, height: 7, pixels: [vec![[255; 3]; 30], vec![[0; 3]; 5]].concat() };
+    let sixel = encode(&image);
+
+    assert!(sixel.starts_with("\x1bPq\"1;1;5;7#0;2;0;0;0"));
+    assert!(sixel.ends_with("#215!5~-#0!5@-\x1b\\"));
+  }
+}
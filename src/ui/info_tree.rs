@@ -0,0 +1,74 @@
+use tui::text::{Span, Spans};
+use mote::devices::Device;
+
+/// A named group of `label: value` lines. Values the device did not report are left out.
+struct Branch {
+  title: &'static str,
+  leaves: Vec<(&'static str, String)>
+}
+
+impl Branch {
+  fn new(title: &'static str, leaves: Vec<(&'static str, Option<String>)>) -> Self {
+    Branch {
+      title,
+      leaves: leaves.into_iter().filter_map(|(label, value)| Some((label, value?))).collect()
+    }
+  }
+}
+
+fn yes_no(value: bool) -> String { if value { "yes" } else { "no" }.into() }
+
+/// The detailed device info view: everything known about the device, as a tree.
+pub fn info_tree(device: &Device) -> Vec<Spans<'static>> {
+  let info = device.info();
+  let system = &info.system;
+
+  let branches: Vec<Branch> = vec![
+    Branch::new("Network", vec![
+      ("Name",        Some(info.network.network_name.clone())),
+      ("Type",        Some(info.network.network_type.to_string())),
+      ("Address",     Some(device.address())),
+      ("MAC Address", Some(info.network.mac_address.clone()))
+    ]),
+    Branch::new("Product", vec![
+      ("Platform",      Some(device.device_type().to_string())),
+      ("Kind",          Some(info.product.form_factor.to_string())),
+      ("Vendor",        Some(info.product.vendor.clone())),
+      ("Model Name",    Some(info.product.model.name.clone())),
+      ("Model Number",  Some(info.product.model.number.clone())),
+      ("Serial Number", Some(info.product.serial_number.clone()))
+    ]),
+    Branch::new("System", vec![
+      ("Software",       system.software_version.clone()),
+      ("Power",          system.power_mode.map(|mode| mode.to_string())),
+      ("Resolution",     system.resolution.clone()),
+      ("Time Zone",      system.time_zone.clone()),
+      ("Developer Mode", system.developer_mode.map(yes_no)),
+      ("Network Control", system.limited_control.map(|limited| if limited { "limited" } else { "full" }.into())),
+      ("Uptime",         Some(system.uptime.as_ref().map(|u| u.pretty()).unwrap_or_else(|| "unknown".into())))
+    ]),
+    Branch {
+      title: "Features",
+      leaves: info.features.iter().map(|(feature, supported)| (*feature, yes_no(*supported))).collect()
+    }
+  ]
+  .into_iter()
+  .filter(|branch| !branch.leaves.is_empty())
+  .collect();
+
+  let mut lines = vec![Spans::from(Span::raw(format!(" {}", info.name)))];
+
+  for (i, branch) in branches.iter().enumerate() {
+    let last_branch = i == branches.len() - 1;
+    let (branch_glyph, indent) = if last_branch { ("└──", "    ") } else { ("├──", "│   ") };
+
+    lines.push(Spans::from(Span::raw(format!(" {} {}", branch_glyph, branch.title))));
+
+    for (j, (label, value)) in branch.leaves.iter().enumerate() {
+      let leaf_glyph = if j == branch.leaves.len() - 1 { "└──" } else { "├──" };
+      lines.push(Spans::from(Span::raw(format!(" {}{} {}: {}", indent, leaf_glyph, label, value))));
+    }
+  }
+
+  lines
+}
@@ -0,0 +1,145 @@
+use tui::{layout::Rect, style::Color, text::Spans};
+use mote::devices::Key;
+
+const REMOTE_ASPECT_RATIO: f64 = 2.0 / 5.5;
+const REMOTE_WIDTH_PIXELS: f64 = 512.0;
+
+/// Height of the device tab bar, including its border
+const TABS_HEIGHT: u16 = 3;
+
+/// A single drawable, clickable button on the remote.
+#[derive(Debug, Clone)]
+pub struct Button {
+  pub key: Key,
+  pub label: &'static str,
+  pub area: Rect,
+
+  /// Background color when the button is not lit
+  pub idle: Color,
+  pub foreground: Color
+}
+
+/// Where everything on screen goes. Computed once per frame, used both to draw and to
+/// hit-test mouse clicks, so what the user clicks is exactly what they see.
+#[derive(Debug, Clone)]
+pub struct RemoteLayout {
+  pub tabs: Rect,
+  pub info: Rect,
+  pub remote: Rect,
+  pub buttons: Vec<Button>,
+  pub status: Rect,
+
+  /// Terminal width in cells
+  pub width: u16
+}
+
+impl RemoteLayout {
+  /// Lays out the screen for the current terminal size, leaving `info_height` lines for the info pane.
+  pub fn new(info_height: u16) -> Self {
+    let (terminal_char_width, terminal_char_height) = termion::terminal_size().expect("Failed to get information about terminal size (in chars)");
+    let (terminal_px_width, terminal_px_height) = termion::terminal_size_pixels().expect("Failed to get information about terminal size (in pixels)");
+
+    let terminal_font_px_width = (terminal_px_width as f64) / (terminal_char_width as f64);
+    let terminal_font_px_height = (terminal_px_height as f64) / (terminal_char_height as f64);
+
+    let remote_char_width = REMOTE_WIDTH_PIXELS / terminal_font_px_width;
+    let remote_char_height = REMOTE_ASPECT_RATIO * REMOTE_WIDTH_PIXELS / terminal_font_px_height;
+
+    let remote_y = TABS_HEIGHT + info_height;
+    // do not respect the exact ratio, it looks ugly because it ends up such an odd line-snapping
+    let remote_width = 1 + remote_char_width.round() as u16;
+    let remote_height = 1 + remote_char_height.round() as u16;
+
+    // the direction pads
+    let dirpad_y_offset = remote_height / 2 - 1;
+    let dirpad_x_offset = 4;
+    let pad_width       = 7;
+    let pad_height      = 3;
+    let x_extension     = 7;
+    let y_extension     = 3;
+
+    // the back, home, power and volume buttons
+    let buttons_x_offset  = remote_width - 45;
+    let buttons_y_offset  = remote_y + dirpad_y_offset - y_extension + 1;
+    let button_pad_width  = 8;
+    let button_pad_height = 3;
+    let button_pad_margin = 3;
+    let button_column     = |i: u16| buttons_x_offset + i * (button_pad_width + button_pad_margin);
+    let button_row        = |i: u16| buttons_y_offset + i * (button_pad_height + 1);
+
+    let pad = |key, label, x, y| Button { key, label, area: Rect::new(x, y, pad_width, pad_height), idle: Color::LightBlue, foreground: Color::White };
+    let button = |key, label, column, row, foreground| Button {
+      key,
+      label,
+      area: Rect::new(button_column(column), button_row(row), button_pad_width, button_pad_height),
+      idle: Color::DarkGray,
+      foreground
+    };
+
+    let buttons = vec![
+      pad(Key::Up,    "\nW",     dirpad_x_offset + x_extension,     remote_y + dirpad_y_offset - y_extension),
+      pad(Key::Down,  "\nS",     dirpad_x_offset + x_extension,     remote_y + dirpad_y_offset + y_extension),
+      pad(Key::Left,  "\nA",     dirpad_x_offset,                   remote_y + dirpad_y_offset),
+      pad(Key::Right, "\nD",     dirpad_x_offset + 2 * x_extension, remote_y + dirpad_y_offset),
+      pad(Key::Ok,       "\nSPACE", dirpad_x_offset + x_extension,     remote_y + dirpad_y_offset),
+
+      button(Key::Back,          "\n⌫",  0, 0, Color::White),
+      button(Key::Home,          "\nH",  1, 0, Color::White),
+      button(Key::Power,         "\nP",  2, 0, Color::LightRed),
+      button(Key::VolumeUp,      "\nV+", 3, 0, Color::White),
+      button(Key::InstantReplay, "\n↺",  0, 1, Color::White),
+      button(Key::Info,          "\n*",  1, 1, Color::White),
+      button(Key::VolumeMute,    "\nM",  2, 1, Color::White),
+      button(Key::VolumeDown,    "\nV-", 3, 1, Color::White)
+    ];
+
+    RemoteLayout {
+      tabs: Rect::new(0, 0, remote_char_width.round() as u16, TABS_HEIGHT),
+      info: Rect::new(0, TABS_HEIGHT, terminal_char_width, info_height),
+      remote: Rect::new(0, remote_y, remote_width, remote_height),
+      buttons,
+      status: Rect::new(0, remote_y + remote_height, terminal_char_width, 1),
+      width: terminal_char_width
+    }
+  }
+
+  /// Room for a panel (a menu, an icon) one cell right of `area`, level with the top of
+  /// the remote, clipped to the screen. `None` when too little of it would fit.
+  pub fn beside(&self, area: Rect, width: u16, height: u16) -> Option<Rect> {
+    let x = area.right() + 1;
+    let width = width.min(self.width.saturating_sub(x));
+
+    (width > 2).then(|| Rect::new(x, self.remote.y, width, height))
+  }
+
+  /// The button drawn under a (zero-based) cell, if any
+  pub fn button_at(&self, column: u16, row: u16) -> Option<Key> {
+    self.buttons
+      .iter()
+      .find(|b| contains(b.area, column, row))
+      .map(|b| b.key)
+  }
+
+  /// The device tab drawn under a (zero-based) cell, if any. Mirrors how `tui::widgets::Tabs`
+  /// places titles: one cell of padding either side, separated by a one cell divider.
+  pub fn tab_at(&self, titles: &[Spans], column: u16, row: u16) -> Option<usize> {
+    let inner = Rect::new(self.tabs.x + 1, self.tabs.y + 1, self.tabs.width.saturating_sub(2), 1);
+    if !contains(inner, column, row) { return None }
+
+    let mut x = inner.x;
+    for (i, title) in titles.iter().enumerate() {
+      // padding, title, padding
+      let end = x + 1 + title.width() as u16 + 1;
+      if column >= x && column < end { return Some(i) }
+
+      // skip the divider
+      x = end + 1;
+    }
+
+    None
+  }
+}
+
+fn contains(area: Rect, column: u16, row: u16) -> bool {
+  column >= area.left() && column < area.right() && row >= area.top() && row < area.bottom()
+}
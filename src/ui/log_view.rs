@@ -0,0 +1,136 @@
+use std::{collections::VecDeque, fs::File, io::{self, Write}, path::Path};
+use mote::devices::LogLine;
+
+/// Most lines the log pane keeps; older ones are dropped
+const LOG_CAPACITY: usize = 10_000;
+
+/// The log pane's view of a device's debug console: everything received so far, narrowed
+/// by a filter and scrolled back from the newest line.
+#[derive(Debug, Clone)]
+pub struct LogView {
+  /// Serial number of the device whose console this is
+  pub device: String,
+
+  lines: VecDeque<LogLine>,
+
+  /// Only lines containing this are shown, ignoring case
+  filter: String,
+
+  /// Filter being typed, while filter entry is open
+  pub editing: Option<String>,
+
+  /// How many matching lines the view is scrolled back; 0 follows new lines as they arrive
+  scroll: usize,
+
+  /// Whether the console is still connected
+  pub connected: bool
+}
+
+impl LogView {
+  pub fn new(device: String) -> Self {
+    LogView { device, lines: VecDeque::new(), filter: String::new(), editing: None, scroll: 0, connected: true }
+  }
+
+  pub fn filter(&self) -> &str { &self.filter }
+
+  pub fn is_following(&self) -> bool { self.scroll == 0 }
+
+  /// Adds a line. A view scrolled back stays on the lines it shows.
+  pub fn push(&mut self, line: LogLine) {
+    if self.scroll > 0 && line.matches(&self.filter) { self.scroll += 1; }
+
+    self.lines.push_back(line);
+    if self.lines.len() > LOG_CAPACITY { self.lines.pop_front(); }
+
+    self.scroll = self.scroll.min(self.matching().count().saturating_sub(1));
+  }
+
+  fn matching(&self) -> impl Iterator<Item = &LogLine> {
+    self.lines.iter().filter(|line| line.matches(&self.filter))
+  }
+
+  /// The matching lines that fit in `rows`, oldest first
+  pub fn visible(&self, rows: usize) -> Vec<&LogLine> {
+    let matching: Vec<_> = self.matching().collect();
+    let end = matching.len() - self.scroll.min(matching.len());
+
+    matching[end.saturating_sub(rows)..end].to_vec()
+  }
+
+  /// Scrolls back `rows`, stopping at the oldest line
+  pub fn scroll_up(&mut self, rows: usize) {
+    self.scroll = (self.scroll + rows).min(self.matching().count().saturating_sub(1));
+  }
+
+  /// Scrolls forward `rows`, following new lines again once at the bottom
+  pub fn scroll_down(&mut self, rows: usize) {
+    self.scroll = self.scroll.saturating_sub(rows);
+  }
+
+  /// Applies the filter being typed, back at the newest line
+  pub fn apply_filter(&mut self) {
+    if let Some(filter) = self.editing.take() {
+      self.filter = filter;
+      self.scroll = 0;
+    }
+  }
+
+  /// Writes every line received, filtered or not, to `path`. Returns how many were written.
+  pub fn save(&self, path: &Path) -> io::Result<usize> {
+    let mut file = io::BufWriter::new(File::create(path)?);
+    for line in &self.lines { writeln!(file, "{}", line)?; }
+    file.flush()?;
+
+    Ok(self.lines.len())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn log(lines: &[&str]) -> LogView {
+    let mut log = LogView::new("X00400ABCDEF".into());
+    for line in lines { log.push(LogLine::now(line.to_string())); }
+    log
+  }
+
+  fn texts(lines: Vec<&LogLine>) -> Vec<&str> {
+    lines.into_iter().map(|line| line.text.as_str()).collect()
+  }
+
+  #[test]
+  fn follows_the_newest_lines() {
+    let log = log(&["one", "two", "three"]);
+
+    assert_eq!(texts(log.visible(2)), vec!["two", "three"]);
+    assert_eq!(texts(log.visible(10)), vec!["one", "two", "three"]);
+  }
+
+  #[test]
+  fn stays_put_when_scrolled_back() {
+    let mut log = log(&["one", "two", "three"]);
+
+    log.scroll_up(1);
+    log.push(LogLine::now("four".into()));
+    assert_eq!(texts(log.visible(2)), vec!["one", "two"]);
+
+    // can't scroll past the oldest line
+    log.scroll_up(10);
+    assert_eq!(texts(log.visible(2)), vec!["one"]);
+
+    log.scroll_down(10);
+    assert!(log.is_following());
+    assert_eq!(texts(log.visible(2)), vec!["three", "four"]);
+  }
+
+  #[test]
+  fn filters_ignoring_case() {
+    let mut log = log(&["[beacon.signal] |AppLaunchInitiate", "hello", "BEACON report"]);
+
+    log.editing = Some("beacon".into());
+    log.apply_filter();
+
+    assert_eq!(texts(log.visible(10)), vec!["[beacon.signal] |AppLaunchInitiate", "BEACON report"]);
+  }
+}
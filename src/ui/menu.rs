@@ -0,0 +1,81 @@
+use mote::devices::DeviceInput;
+
+/// One pickable line of a menu, and what picking it sends to the device
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MenuEntry {
+  pub label: String,
+  pub input: DeviceInput,
+
+  /// App whose icon is shown beside the menu while this entry is highlighted
+  pub icon: Option<String>
+}
+
+/// A pick-one list shown beside the remote, such as the channel list or the input switcher.
+#[derive(Debug, Clone)]
+pub struct Menu {
+  pub title: &'static str,
+  pub entries: Vec<MenuEntry>,
+  selected: usize
+}
+
+impl Menu {
+  /// A menu with `selected` highlighted, clamped to the entries there are
+  pub fn new(title: &'static str, entries: Vec<MenuEntry>, selected: usize) -> Self {
+    let selected = selected.min(entries.len().saturating_sub(1));
+    Menu { title, entries, selected }
+  }
+
+  pub fn selected_index(&self) -> usize { self.selected }
+
+  pub fn selected(&self) -> Option<&MenuEntry> { self.entries.get(self.selected) }
+
+  /// Moves the highlight down, wrapping to the top.
+  pub fn next(&mut self) {
+    if self.entries.is_empty() { return }
+    self.selected = (self.selected + 1) % self.entries.len();
+  }
+
+  /// Moves the highlight up, wrapping to the bottom.
+  pub fn previous(&mut self) {
+    if self.entries.is_empty() { return }
+    self.selected = (self.selected + self.entries.len() - 1) % self.entries.len();
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn menu(entries: usize, selected: usize) -> Menu {
+    let entries = (0..entries)
+      .map(|i| MenuEntry { label: i.to_string(), input: DeviceInput::Tune(i.to_string()), icon: None })
+      .collect();
+
+    Menu::new("Channels", entries, selected)
+  }
+
+  #[test]
+  fn wraps_around() {
+    let mut menu = menu(3, 2);
+
+    menu.next();
+    assert_eq!(menu.selected_index(), 0);
+
+    menu.previous();
+    assert_eq!(menu.selected_index(), 2);
+  }
+
+  #[test]
+  fn clamps_initial_selection() {
+    assert_eq!(menu(3, 7).selected_index(), 2);
+  }
+
+  #[test]
+  fn empty_menus_select_nothing() {
+    let mut menu = menu(0, 0);
+
+    menu.next();
+    menu.previous();
+    assert_eq!(menu.selected(), None);
+  }
+}
@@ -0,0 +1,86 @@
+use super::graphics::Image;
+
+/// The developer panel's view of a device's screen. Captures take a while, so asking for
+/// more while one runs is folded into a single follow-up capture instead of piling up.
+#[derive(Debug, Clone)]
+pub struct Mirror {
+  /// Serial number of the device being mirrored
+  pub device: String,
+
+  /// The latest capture, if any has succeeded yet
+  pub screen: Option<Image>,
+
+  /// How many captures have succeeded, so each new one is drawn afresh
+  pub captures: usize,
+
+  capturing: bool,
+
+  /// Another capture was asked for while one was running
+  stale: bool
+}
+
+impl Mirror {
+  pub fn new(device: String) -> Self {
+    Mirror { device, screen: None, captures: 0, capturing: false, stale: false }
+  }
+
+  pub fn is_capturing(&self) -> bool { self.capturing }
+
+  /// Asks for a fresh capture. True if one should start now; otherwise one is already
+  /// running and another will follow it.
+  pub fn request(&mut self) -> bool {
+    if self.capturing {
+      self.stale = true;
+      return false
+    }
+
+    self.capturing = true;
+    true
+  }
+
+  /// Records how a capture went. True if another was asked for meanwhile and should be requested now.
+  pub fn captured(&mut self, screen: Option<Image>) -> bool {
+    if let Some(screen) = screen {
+      self.screen = Some(screen);
+      self.captures += 1;
+    }
+
+    self.capturing = false;
+    std::mem::take(&mut self.stale)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn folds_requests_made_while_capturing() {
+    let mut mirror = Mirror::new("X00400ABCDEF".into());
+    let screen = Image { width: 1, height: 1, pixels: vec![[0; 3]] };
+
+    assert!(mirror.request());
+    assert!(!mirror.request());
+    assert!(!mirror.request());
+
+    // one follow-up for both requests, then quiet
+    assert!(mirror.captured(Some(screen.clone())));
+    assert!(mirror.request());
+    assert!(!mirror.captured(Some(screen)));
+    assert!(!mirror.is_capturing());
+    assert_eq!(mirror.captures, 2);
+  }
+
+  #[test]
+  fn keeps_the_last_screen_when_a_capture_fails() {
+    let mut mirror = Mirror::new("X00400ABCDEF".into());
+
+    mirror.request();
+    mirror.captured(Some(Image { width: 1, height: 1, pixels: vec![[9; 3]] }));
+    mirror.request();
+    mirror.captured(None);
+
+    assert_eq!(mirror.captures, 1);
+    assert!(mirror.screen.is_some());
+  }
+}
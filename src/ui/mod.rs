@@ -1,111 +1,315 @@
-use futures::future::{select, Either};
-use std::{collections::HashMap, time::Instant};
-use tokio::sync::mpsc::UnboundedReceiver;
-use std::io;
+use std::{collections::{HashMap, HashSet}, path::PathBuf, time::{Duration, Instant, SystemTime, UNIX_EPOCH}};
+use futures::future::join_all;
+use tokio::{sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender}, task::JoinHandle, time::timeout};
+use std::io::{self, Write};
 use std::sync::{Arc, Mutex};
-use termion::{event::Key, raw::{IntoRawMode, RawTerminal}};
+use termion::{input::MouseTerminal, raw::{IntoRawMode, RawTerminal}};
 use tui::{
   Terminal, 
   backend::TermionBackend, 
   layout::{Alignment, Rect}, 
   style::{Color, Style}, 
   text::{Span, Spans}, 
-  widgets::{Block, BorderType, Borders, Paragraph, Tabs},
+  widgets::{Block, BorderType, Borders, List, ListItem, ListState, Paragraph, Tabs},
   symbols::line::VERTICAL
 };
-use tokio_stream::{wrappers::UnboundedReceiverStream, StreamExt};
-use crate::devices::device_input::DeviceInput;
-use self::user_input::user_input;
+use self::{event::Event, graphics::{half_blocks, Image, Protocol}, info_tree::info_tree, layout::RemoteLayout, log_view::LogView, menu::{Menu, MenuEntry}, mirror::Mirror, selection::{Identified, Selection}, terminal_guard::TerminalGuard, user_input::{user_input, Key, UserInput}};
 
-use super::devices::{Device, roku::RokuKey};
+use mote::devices::{discovery::REQUEST_TIMEOUT, App, AppState, Capability, Channel, Device, DeviceInput, DeviceType, Key as RemoteKey, LogLine, SendQueue, TvInput};
+use crate::harness::Recorder;
+mod event;
+mod graphics;
+pub mod info_tree;
+mod layout;
+mod log_view;
+mod menu;
+mod mirror;
+mod selection;
+mod terminal_guard;
 mod user_input;
 
-const REMOTE_ASPECT_RATIO: f64 = 2.0 / 5.5;
-const REMOTE_WIDTH_PIXELS: f64 = 512.0;
+/// How long a button stays lit after its request succeeded
+const KEY_FLASH_DURATION: Duration = Duration::from_millis(250);
+
+/// How long a button stays lit after its request failed, long enough to notice
+const KEY_FAILURE_FLASH_DURATION: Duration = Duration::from_millis(750);
+
+/// Frames of the animation shown while no devices have been found
+const SEARCHING_FRAMES: [&str; 4] = ["   ", ".  ", ".. ", "..."];
+
+/// How long in-flight keypresses get to finish when quitting
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(1);
+
+/// How long a message stays in the status line
+const STATUS_DURATION: Duration = Duration::from_secs(5);
+
+/// Widest a menu is drawn, in cells
+const MENU_WIDTH: u16 = 32;
+
+/// Height of the app icon beside the launcher, in cells
+const ICON_ROWS: u16 = 8;
+
+/// How long a screen gets to settle after a keypress before it is captured
+const SCREENSHOT_SETTLE: Duration = Duration::from_millis(400);
+
+/// How far PageUp and PageDown move the log pane, in lines
+const LOG_SCROLL_LINES: usize = 10;
+
+/// How to lift a Roku's Limited mode, shown in the info pane while it refuses control
+const ROKU_LIMITED_GUIDE: [&str; 3] = [
+  " ⚠ This Roku is in Limited mode and refuses keypresses from the network. To allow mote, go to",
+  "   Settings > System > Advanced system settings > Control by mobile apps > Network access",
+  "   on the Roku and choose Permissive."
+];
+
+/// App icons are cached per device serial number and app id
+type IconKey = (String, String);
 
 enum UIContext { Main, DeviceInfo }
 
+/// A picture to draw, named so an unchanged one is not drawn again
+#[derive(Clone)]
+struct Picture { id: String, image: Image, area: Rect }
+
+/// The developer panel's border, which the screenshot is drawn inside
+struct ScreenFrame { title: &'static str, area: Rect }
+
+/// The log pane's border and the lines that fit inside it
+struct LogPane { title: String, lines: Vec<Spans<'static>>, area: Rect }
+
+/// Where a pressed button's request is in its lifecycle, which decides how it is lit.
+#[derive(Debug, Clone, Copy)]
+enum Press { Pending, Delivered, Failed }
+
+impl Press {
+  /// Background color for a lit button, or `idle` once the flash has decayed
+  fn color(press: Option<&(Press, Instant)>, idle: Color) -> Color {
+    match press {
+      None => idle,
+      Some((Press::Pending, _)) => Color::Blue,
+      Some((Press::Delivered, _)) => Color::Green,
+      Some((Press::Failed, _)) => Color::Red
+    }
+  }
+
+  /// Whether a flash that started at `since` should still be shown
+  fn is_live(&self, since: Instant) -> bool {
+    match self {
+      // the request has timed out by then, whether or not its result arrived
+      Press::Pending => since.elapsed() < REQUEST_TIMEOUT,
+      Press::Delivered => since.elapsed() < KEY_FLASH_DURATION,
+      Press::Failed => since.elapsed() < KEY_FAILURE_FLASH_DURATION
+    }
+  }
+}
+
+/// Whether a status message reports a failure or just says what happened
+#[derive(Debug, Clone, Copy)]
+enum Severity { Info, Error }
+
+impl Severity {
+  fn color(self) -> Color {
+    match self {
+      Severity::Info => Color::Gray,
+      Severity::Error => Color::LightRed
+    }
+  }
+}
+
+/// A device error for the status line, followed by what to do about it where that is known
+fn explain(e: &mote::Error) -> String {
+  match e.hint() {
+    Some(hint) => format!("{} ({})", e, hint),
+    None => e.to_string()
+  }
+}
+
 pub struct UI {
   /// terminal abstraction
-  terminal: Terminal<TermionBackend<RawTerminal<std::io::Stdout>>>,
+  terminal: Terminal<TermionBackend<MouseTerminal<RawTerminal<std::io::Stdout>>>>,
+
+  /// restores the terminal on drop and on panic, so declared after `terminal`
+  _guard: TerminalGuard,
 
-  /// Device states
-  devices: Vec<Device>,
+  /// Device states, and which one is active
+  devices: Selection<Device>,
 
-  /// Which device is active
-  selected_device_index: usize,
+  /// When the UI came up, drives the searching animation
+  started: Instant,
 
   /// Which subscreen the user is viewing
   context: UIContext,
+
+  /// Where things were last drawn, for hit-testing clicks
+  layout: Option<RemoteLayout>,
   
   /// track the active keys for rendering purposes
-  active_keys: Arc<Mutex<HashMap<Key, (bool, Instant)>>>
+  active_keys: Arc<Mutex<HashMap<RemoteKey, (Press, Instant)>>>,
+
+  /// Most recent message for the status line, how serious it is, and when it arrived
+  status: Option<(String, Severity, Instant)>,
+
+  /// Text being typed for the selected device, while text entry is open
+  typing: Option<String>,
+
+  /// Pick-one list open beside the remote (channels, inputs), which takes the keyboard while open
+  menu: Option<Menu>,
+
+  /// How this terminal draws pictures
+  graphics: Protocol,
+
+  /// App icons fetched so far; `None` while loading, or for apps without one
+  icons: HashMap<IconKey, Option<Image>>,
+
+  /// Developer panel mirroring the selected device's screen, while open
+  mirror: Option<Mirror>,
+
+  /// Log pane showing the selected device's debug console, while open
+  log: Option<LogView>,
+
+  /// Streams the log pane's console into the event channel; aborting it hangs up
+  log_task: Option<JoinHandle<()>>,
+
+  /// Records the session for replaying later, while recording
+  recorder: Option<Recorder>,
+
+  /// Devices that refused control from this computer, by serial number, until one accepts a keypress
+  refused: HashSet<String>,
+
+  /// Inputs on their way to each device, by serial number, delivered in the order they were sent
+  queues: HashMap<String, SendQueue>,
+
+  /// Pictures drawn with escapes that are on screen now, and where
+  shown_pictures: Vec<(String, Rect)>,
+
+  /// Event channel, background tasks report back through these
+  events: UnboundedReceiver<Event>,
+  sender: UnboundedSender<Event>,
+
+  /// Long-lived background tasks (discovery, polling, ticks), stopped on shutdown
+  tasks: Vec<JoinHandle<()>>,
+
+  /// Keypress requests still in flight, given a chance to finish on shutdown
+  pending: Vec<JoinHandle<()>>
 }
 
 impl UI {
   pub fn new() -> Self {
+    let guard = TerminalGuard::new();
+
     let stdout = io::stdout()
       .into_raw_mode()
       .expect("Failed to put terminal into 'raw mode'");
     
-    let backend = TermionBackend::new(stdout);
+    let backend = TermionBackend::new(MouseTerminal::from(stdout));
     let mut terminal = Terminal::new(backend).expect("Failed to initialize terminal abstraction");
     
     terminal.clear().expect("Failed to clear terminal");
     terminal.hide_cursor().expect("Failed to hide cursor");
 
-    println!("Searching. Devices appear as they're discovered.");
+    let (sender, events) = unbounded_channel();
 
     UI {
       terminal,
-      devices: vec![],
-      selected_device_index: 0,
+      _guard: guard,
+      devices: Selection::new(),
+      started: Instant::now(),
       context: UIContext::Main,
-      active_keys: Arc::from(Mutex::from(HashMap::new()))
+      layout: None,
+      active_keys: Arc::from(Mutex::from(HashMap::new())),
+      status: None,
+      typing: None,
+      menu: None,
+      graphics: Protocol::detect(),
+      icons: HashMap::new(),
+      mirror: None,
+      log: None,
+      log_task: None,
+      recorder: None,
+      refused: HashSet::new(),
+      queues: HashMap::new(),
+      shown_pictures: vec![],
+      events,
+      sender,
+      tasks: vec![],
+      pending: vec![]
     }
   }
 
+  /// Records every input sent from the remote, and samples of what the device does in response, to `recorder`.
+  pub fn record(mut self, recorder: Recorder) -> Self {
+    self.recorder = Some(recorder);
+    self
+  }
+
   // draw based on state
   fn render(&mut self) {
-    let tab_titles: Vec<Spans> = self.devices.iter().map(|d| Spans::from(d.device_info().name.clone())).collect();
-    let selected_index = self.selected_device_index;
-    let selected_device = &self.devices[selected_index];
-    let ip = selected_device.ip_string().clone();
-    let info = selected_device.device_info();
-    let context = &self.context;
-
-    let (
-      wpad_state, 
-      apad_state, 
-      spad_state, 
-      dpad_state, 
-      kpad_state
-    ) = {
-      let ks = self.active_keys.lock().unwrap();
-      (
-        ks.contains_key(&Key::Char('w')),
-        ks.contains_key(&Key::Char('a')),
-        ks.contains_key(&Key::Char('s')),
-        ks.contains_key(&Key::Char('d')),
-        ks.contains_key(&Key::Char(' '))
-      )
+    let (Some(selected_index), Some(selected_device)) = (self.devices.selected_index(), self.devices.selected()) else {
+      return self.render_searching()
+    };
+
+    let tab_titles = self.tab_titles();
+    let tabs_title = match self.recorder {
+      Some(_) => "Devices (recording)",
+      None => "Devices"
+    };
+    let address = selected_device.address();
+    let info = selected_device.info();
+    let status = match (&self.typing, self.log.as_ref().and_then(|log| log.editing.as_ref())) {
+      (Some(text), _) => Some((format!("Type: {}_", text), Color::White)),
+      (None, Some(filter)) => Some((format!("Filter: {}_", filter), Color::White)),
+      (None, None) => self.status.as_ref().map(|(message, severity, _)| (message.clone(), severity.color()))
+    };
+
+    let menu = self.menu.clone();
+    let flashes = self.active_keys.lock().unwrap().clone();
+    let capabilities = selected_device.capabilities();
+
+    let mut info_contents = match self.context {
+      UIContext::Main => vec![
+        Spans::from(Span::raw(format!(" {} ({})", info.name, address)))
+      ],
+      UIContext::DeviceInfo => info_tree(selected_device)
     };
 
+    if self.is_limited(selected_device) {
+      match self.context {
+        UIContext::DeviceInfo => if selected_device.device_type() == DeviceType::Roku {
+          info_contents.extend(ROKU_LIMITED_GUIDE.map(|line| Spans::from(Span::raw(line))))
+        } else {
+          info_contents.push(Spans::from(Span::raw(" ⚠ This device refuses control from this computer; allow it in the device's network settings")))
+        },
+        UIContext::Main => info_contents.push(Spans::from(Span::raw(" ⚠ Refusing control, press i to see how to allow it")))
+      }
+    }
+
+    if let Some(now_playing) = &info.now_playing {
+      info_contents.push(Spans::from(Span::raw(format!(" ▶ {}", now_playing))));
+    }
+
+    let layout = RemoteLayout::new(info_contents.len() as u16);
+    self.layout = Some(layout.clone());
+
+    let menu_area = menu.as_ref().and_then(|menu| layout.beside(layout.remote, MENU_WIDTH, (menu.entries.len() as u16 + 2).min(layout.remote.height)));
+    let icon = self.highlighted_icon(&layout, menu_area);
+    let screen_frame = self.screen_frame(&layout, icon.as_ref().map(|icon| icon.area).or(menu_area));
+    let log_pane = self.log_pane(&layout, screen_frame.as_ref().map(|(frame, _)| frame.area).or(icon.as_ref().map(|icon| icon.area)).or(menu_area));
+    let pictures: Vec<Picture> = icon.into_iter().chain(screen_frame.as_ref().and_then(|(_, picture)| picture.clone())).collect();
+
+    // half blocks are ordinary text, the other protocols draw over the frame once it is out
+    let (blocks, pictures): (Vec<Picture>, Vec<Picture>) = pictures.into_iter().partition(|_| self.graphics == Protocol::HalfBlocks);
+    let blocks: Vec<_> = blocks
+      .into_iter()
+      .map(|picture| (half_blocks(&picture.image.fit(picture.area.width as u32, picture.area.height as u32 * 2)), picture.area))
+      .collect();
+    self.prepare_pictures(pictures.iter().map(|picture| (picture.id.clone(), picture.area)).collect());
+
     self.terminal.draw(move |f| {
-      let (terminal_char_width, terminal_char_height) = termion::terminal_size().expect("Failed to get information about terminal size (in chars)");
-      let (terminal_px_width, terminal_px_height) = termion::terminal_size_pixels().expect("Failed to get information about terminal size (in pixels)");
-    
-      let terminal_font_px_width = (terminal_px_width as f64) / (terminal_char_width as f64);
-      let terminal_font_px_height = (terminal_px_height as f64) / (terminal_char_height as f64);
-    
-      let remote_char_width = REMOTE_WIDTH_PIXELS / terminal_font_px_width;
-      let remote_char_height = REMOTE_ASPECT_RATIO * REMOTE_WIDTH_PIXELS / terminal_font_px_height;
-    
       let tabs = 
         Tabs::new(tab_titles)
           .block(
             Block::default()
-              .title("Devices")
+              .title(tabs_title)
               .borders(Borders::ALL)
             )
           .style(
@@ -120,43 +324,13 @@ impl UI {
           )
           .divider(VERTICAL)
           .select(selected_index);
-      f.render_widget(tabs, Rect::new(0, 0, remote_char_width.round() as u16, 3));
-
-      let info_contents = match context {
-        UIContext::Main => vec![
-          Spans::from(Span::raw(format!(" {} ({})", info.name, ip)))
-        ],
-        UIContext::DeviceInfo => vec![
-          Spans::from(Span::raw(format!(" {}", info.name))),
-          Spans::from(Span::raw(format!(" ├── Network"))),
-          Spans::from(Span::raw(format!(" │   ├── Name: {}",        info.network.network_name))),
-          Spans::from(Span::raw(format!(" │   ├── Type: {}",        info.network.network_type))),
-          Spans::from(Span::raw(format!(" │   ├── IP: {}",          ip))),
-          Spans::from(Span::raw(format!(" │   └── MAC Address: {}", info.network.mac_address))),
-          Spans::from(Span::raw(format!(" ├── Product"))),
-          Spans::from(Span::raw(format!(" │   ├── Vendor: {}",        info.product.vendor))),
-          Spans::from(Span::raw(format!(" │   ├── Model Name: {}",    info.product.model.name))),
-          Spans::from(Span::raw(format!(" │   ├── Model Number: {}",  info.product.model.number))),
-          Spans::from(Span::raw(format!(" │   └── Serial Number: {}", info.product.serial_number))),
-          Spans::from(Span::raw(format!(" └── System"))),
-          Spans::from(Span::raw(format!("     └── Uptime: {}", match &info.system.uptime {
-            None => "unknown".into(),
-            Some(u) => u.pretty()
-          })))
-        ]
-      };
-      let info_height = info_contents.len() as u16;
+      f.render_widget(tabs, layout.tabs);
 
       // render dynamic info widget
       let info = Paragraph::new(info_contents)
         // .wrap(Wrap { trim: false })
         .alignment(Alignment::Left);
-      f.render_widget(info, Rect::new(0, 3, terminal_char_width, info_height));
-
-      let remote_y = 3 + info_height;
-      // do not respect the exact ratio, it looks ugly because it ends up such an odd line-snapping
-      let remote_width = 1 + remote_char_width.round() as u16;
-      let remote_height = 1 + remote_char_height.round() as u16;
+      f.render_widget(info, layout.info);
 
       let remote_body = Block::default()
         .style(
@@ -167,111 +341,665 @@ impl UI {
         .borders(Borders::ALL)
         .border_type(BorderType::Plain);
 
-      f.render_widget(
-        remote_body,
-        Rect::new(0, remote_y, remote_width, remote_height)
-      );
+      f.render_widget(remote_body, layout.remote);
 
-      // render the direction pads
-      let pad = |name, state| {
-        Paragraph::new(name)
-          .style(
-            Style::default()
-              .bg(if state { Color::Blue } else { Color::LightBlue })
-              .fg(Color::White)
+      // render the pads and buttons, lit according to any in-flight or recent presses,
+      // and greyed out where the device cannot honor them
+      for button in layout.buttons {
+        let style = match capabilities.supports_key(button.key) {
+          true => Style::default().bg(Press::color(flashes.get(&button.key), button.idle)).fg(button.foreground),
+          false => Style::default().bg(Color::Black).fg(Color::DarkGray)
+        };
+
+        let widget = Paragraph::new(button.label)
+          .style(style)
+          .alignment(Alignment::Center);
+
+        f.render_widget(widget, button.area);
+      }
+
+      // render the open menu to the right of the remote, and the highlighted app's icon beside it
+      if let (Some(menu), Some(area)) = (menu, menu_area) {
+        let items: Vec<ListItem> = menu.entries.iter().map(|entry| ListItem::new(entry.label.clone())).collect();
+        let list = List::new(items)
+          .block(
+            Block::default()
+              .title(menu.title)
+              .borders(Borders::ALL)
           )
-          .alignment(Alignment::Center)
-      };
+          .style(Style::default().bg(Color::Black).fg(Color::White))
+          .highlight_style(Style::default().bg(Color::Yellow).fg(Color::Black));
+
+        let mut state = ListState::default();
+        state.select(Some(menu.selected_index()));
+        f.render_stateful_widget(list, area, &mut state);
+      }
+
+      // render the developer panel's frame, its screenshot goes inside
+      if let Some((frame, _)) = screen_frame {
+        f.render_widget(
+          Block::default()
+            .title(frame.title)
+            .borders(Borders::ALL)
+            .style(Style::default().bg(Color::Black).fg(Color::White)),
+          frame.area
+        );
+      }
+
+      // render the log pane, right of everything else
+      if let Some(pane) = log_pane {
+        let log = Paragraph::new(pane.lines)
+          .block(
+            Block::default()
+              .title(pane.title)
+              .borders(Borders::ALL)
+          )
+          .style(Style::default().bg(Color::Black).fg(Color::White));
+
+        f.render_widget(log, pane.area);
+      }
 
-      let w_pad  = pad("\nW", wpad_state);
-      let a_pad  = pad("\nA", apad_state);
-      let s_pad  = pad("\nS", spad_state);
-      let d_pad  = pad("\nD", dpad_state);
-      let ok_pad = pad("\nSPACE", kpad_state);
-
-      let dirpad_y_offset = remote_height / 2 - 1;
-      let dirpad_x_offset = 4;
-      let pad_width       = 7;
-      let pad_height      = 3;
-      let x_extension     = 7;
-      let y_extension     = 3;
-
-      f.render_widget(w_pad,  Rect::new(dirpad_x_offset + x_extension,     remote_y + dirpad_y_offset - y_extension, pad_width, pad_height));
-      f.render_widget(s_pad,  Rect::new(dirpad_x_offset + x_extension,     remote_y + dirpad_y_offset + y_extension, pad_width, pad_height));
-      f.render_widget(a_pad,  Rect::new(dirpad_x_offset + 0,               remote_y + dirpad_y_offset - 0,           pad_width, pad_height));
-      f.render_widget(d_pad,  Rect::new(dirpad_x_offset + 2 * x_extension, remote_y + dirpad_y_offset - 0,           pad_width, pad_height));
-      f.render_widget(ok_pad, Rect::new(dirpad_x_offset + x_extension,     remote_y + dirpad_y_offset - 0,           pad_width, pad_height));
-
-      // render the back and home buttons
-      let buttons_x_offset  = remote_width - 45;
-      let buttons_y_offset  = remote_y + dirpad_y_offset - y_extension + 1;
-      let button_pad_width  = 11;
-      let button_pad_height = 3;
-      let button_pad_margin = 4;
-
-      let back_pad   = Paragraph::new("\n⌫").style(Style::default().bg(Color::DarkGray).fg(Color::White)).alignment(Alignment::Center);
-      let home_pad   = Paragraph::new("\nH").style(Style::default().bg(Color::DarkGray).fg(Color::White)).alignment(Alignment::Center);
-      let power_pad  = Paragraph::new("\nP").style(Style::default().bg(Color::DarkGray).fg(Color::LightRed)).alignment(Alignment::Center);
-
-      f.render_widget(back_pad,   Rect::new(buttons_x_offset,                                            buttons_y_offset, button_pad_width, button_pad_height));
-      f.render_widget(home_pad,   Rect::new(buttons_x_offset + button_pad_width + button_pad_margin,     buttons_y_offset, button_pad_width, button_pad_height));
-      f.render_widget(power_pad,  Rect::new(buttons_x_offset + 2*(button_pad_width + button_pad_margin), buttons_y_offset, button_pad_width, button_pad_height));
-
-      let replay_pad = Paragraph::new("\n↺").style(Style::default().bg(Color::DarkGray).fg(Color::White)).alignment(Alignment::Center);
-      let star_pad   = Paragraph::new("\n*").style(Style::default().bg(Color::DarkGray).fg(Color::White)).alignment(Alignment::Center);
-      let mute_pad   = Paragraph::new("\nM").style(Style::default().bg(Color::DarkGray).fg(Color::White)).alignment(Alignment::Center);
-
-      f.render_widget(replay_pad, Rect::new(buttons_x_offset,                                            buttons_y_offset + button_pad_height + 1, button_pad_width, button_pad_height));
-      f.render_widget(star_pad,   Rect::new(buttons_x_offset + button_pad_width + button_pad_margin,     buttons_y_offset + button_pad_height + 1, button_pad_width, button_pad_height));
-      f.render_widget(mute_pad,   Rect::new(buttons_x_offset + 2*(button_pad_width + button_pad_margin), buttons_y_offset + button_pad_height + 1, button_pad_width, button_pad_height));
+      for (lines, area) in blocks {
+        f.render_widget(Paragraph::new(lines), area);
+      }
+
+      // render the text being typed, or else the most recent background error, if any
+      if let Some((message, color)) = status {
+        let status_line = Paragraph::new(format!(" {}", message)).style(Style::default().fg(color));
+        f.render_widget(status_line, layout.status);
+      }
     })
-    .expect("Failed to render")
+    .expect("Failed to render");
+
+    self.draw_pictures(pictures);
+  }
+
+  /// The icon of the highlighted menu entry, once it has loaded. Asks for it the first time it is wanted.
+  fn highlighted_icon(&mut self, layout: &RemoteLayout, menu_area: Option<Rect>) -> Option<Picture> {
+    let app_id = self.menu.as_ref()?.selected()?.icon.clone()?;
+    let device = self.devices.selected()?.clone();
+    let key = (device.id(), app_id);
+
+    let Some(cached) = self.icons.get(&key) else {
+      self.fetch_icon(device, key);
+      return None
+    };
+
+    // a box of the icons' usual 4:3, measured in cells
+    let (cell_width, cell_height) = graphics::cell_pixels();
+    let columns = (ICON_ROWS as u32 * cell_height * 4 / (3 * cell_width.max(1))) as u16;
+    let area = layout.beside(menu_area?, columns, ICON_ROWS)?;
+
+    cached.clone().map(|image| Picture { id: format!("icon {} {}", key.0, key.1), image, area })
   }
 
-  fn send(&mut self, input: DeviceInput) {
-    if let Some(device) = self.devices.get(self.selected_device_index) {
-      device.send_input(input)
+  /// The developer panel beside `after` (or the remote), and the latest screenshot inside it.
+  fn screen_frame(&self, layout: &RemoteLayout, after: Option<Rect>) -> Option<(ScreenFrame, Option<Picture>)> {
+    let mirror = self.mirror.as_ref()?;
+
+    // a 16:9 screen, as tall as the remote
+    let (cell_width, cell_height) = graphics::cell_pixels();
+    let rows = layout.remote.height.saturating_sub(2);
+    let columns = (rows as u32 * cell_height * 16 / (9 * cell_width.max(1))) as u16;
+    let area = layout.beside(after.unwrap_or(layout.remote), columns + 2, rows + 2)?;
+
+    let title = match mirror.is_capturing() {
+      true => "Screen (capturing)",
+      false => "Screen"
+    };
+
+    let inner = Rect::new(area.x + 1, area.y + 1, area.width.saturating_sub(2), area.height.saturating_sub(2));
+    let picture = mirror.screen.clone().map(|image| Picture { id: format!("screen {} {}", mirror.device, mirror.captures), image, area: inner });
+
+    Some((ScreenFrame { title, area }, picture))
+  }
+
+  /// The log pane beside `after` (or the remote), filled with the newest matching lines that fit.
+  fn log_pane(&self, layout: &RemoteLayout, after: Option<Rect>) -> Option<LogPane> {
+    let log = self.log.as_ref()?;
+    let area = layout.beside(after.unwrap_or(layout.remote), layout.width, layout.remote.height)?;
+
+    let mut title = "Debug console".to_owned();
+    if !log.filter().is_empty() { title += &format!(" [{}]", log.filter()); }
+    if !log.is_following() { title += " (scrolled back)"; }
+    if !log.connected { title += " (disconnected)"; }
+
+    let lines = log
+      .visible(area.height.saturating_sub(2) as usize)
+      .into_iter()
+      .map(|line| Spans::from(vec![
+        Span::styled(line.timestamp(), Style::default().fg(Color::DarkGray)),
+        Span::raw(format!(" {}", line.text))
+      ]))
+      .collect();
+
+    Some(LogPane { title, lines, area })
+  }
+
+  /// Fetches and decodes an app icon in the background. Failures are cached as `None`, so
+  /// an app without an icon is asked for it once rather than on every frame.
+  fn fetch_icon(&mut self, device: Device, key: IconKey) {
+    self.icons.insert(key.clone(), None);
+
+    let sender = self.sender.clone();
+    self.pending.push(tokio::spawn(async move {
+      let icon = device.icon(&key.1).await.and_then(|png| Image::decode_png(&png));
+      let _ = sender.send(Event::IconLoaded(key.0, key.1, icon));
+    }));
+  }
+
+  /// Before a frame is drawn: if the pictures on screen are about to change, removes them all.
+  fn prepare_pictures(&mut self, wanted: Vec<(String, Rect)>) {
+    if self.shown_pictures.is_empty() || self.shown_pictures == wanted { return }
+
+    match self.graphics.clear() {
+      Some(clear) => { let _ = write!(self.terminal.backend_mut(), "{}", clear); },
+      // redrawing every cell is what removes sixel and iTerm pictures
+      None => { let _ = self.terminal.clear(); }
+    }
+
+    self.shown_pictures.clear();
+  }
+
+  /// After a frame is drawn: draws pictures with the terminal's graphics protocol, once per
+  /// change rather than every frame, since tui leaves cells it did not change alone.
+  fn draw_pictures(&mut self, pictures: Vec<Picture>) {
+    // `prepare_pictures` leaves nothing shown unless what is shown is what is wanted
+    if !self.shown_pictures.is_empty() { return }
+
+    let (cell_width, cell_height) = graphics::cell_pixels();
+    let backend = self.terminal.backend_mut();
+
+    for Picture { id, image, area } in pictures {
+      let fitted = image.fit(area.width as u32 * cell_width, area.height as u32 * cell_height);
+      let Some(escape) = self.graphics.escape(&fitted, area.width, area.height) else { continue };
+
+      let _ = write!(backend, "{}{}", termion::cursor::Goto(area.x + 1, area.y + 1), escape);
+      self.shown_pictures.push((id, area));
     }
+
+    let _ = backend.flush();
   }
 
-  async fn on_key(&mut self, key: Key) -> bool {
+  /// Placeholder screen for while discovery has not found anything yet.
+  fn render_searching(&mut self) {
+    self.layout = None;
+    self.prepare_pictures(vec![]);
+
+    let frame = SEARCHING_FRAMES[(self.started.elapsed().as_millis() / 400) as usize % SEARCHING_FRAMES.len()];
+    let message = vec![
+      Spans::from(Span::raw(format!(" Searching{}", frame))),
+      Spans::from(Span::raw(" Devices appear as they're discovered."))
+    ];
+
+    self.terminal.draw(move |f| {
+      let area = f.size();
+      let searching = Paragraph::new(message)
+        .block(
+          Block::default()
+            .title("Devices")
+            .borders(Borders::ALL)
+        )
+        .alignment(Alignment::Left);
+
+      f.render_widget(searching, tui::layout::Rect::new(0, 0, area.width.min(48), 4));
+    })
+    .expect("Failed to render");
+  }
+
+  fn tab_titles(&self) -> Vec<Spans<'static>> {
+    self.devices
+      .items()
+      .iter()
+      .map(|d| match self.is_limited(d) {
+        true => Spans::from(format!("{} (limited)", d.info().name)),
+        false => Spans::from(d.info().name.clone())
+      })
+      .collect()
+  }
+
+  /// Whether the device refuses control from this computer, as it reports or as it last answered a keypress
+  fn is_limited(&self, device: &Device) -> bool {
+    device.info().system.limited_control == Some(true) || self.refused.contains(&device.id())
+  }
+
+  /// Sends a key to the selected device, lighting its button until the request resolves.
+  /// Keys the device cannot honor are refused here rather than sent to fail.
+  fn press(&mut self, key: RemoteKey) {
+    let Some(device) = self.devices.selected().cloned() else { return };
+
+    if !device.capabilities().supports_key(key) {
+      self.status = Some((format!("{} has no {:?} key", device.info().name, key), Severity::Error, Instant::now()));
+      return
+    }
+
+    self.record_input(&device, &key.into());
+    self.active_keys.lock().unwrap().insert(key, (Press::Pending, Instant::now()));
+
+    let delivered = self.queue(&device).send(device.clone(), key.into());
+    let sender = self.sender.clone();
+    self.pending.push(tokio::spawn(async move {
+      let result = delivered.await;
+      let _ = sender.send(Event::KeySent(device.id(), key, result));
+    }));
+  }
+
+  /// The device's send queue, started with its first input. Each input is given the device as
+  /// last polled, so one that moved to a new address is sent to there.
+  fn queue(&mut self, device: &Device) -> &SendQueue {
+    self.queues.entry(device.id()).or_default()
+  }
+
+  /// Adds an input to the session being recorded, if any.
+  fn record_input(&mut self, device: &Device, input: &DeviceInput) {
+    let Some(recorder) = &mut self.recorder else { return };
+
+    match recorder.input(device.info(), input) {
+      Ok(true) => (),
+      Ok(false) => self.status = Some((format!("Only the first device used is recorded, not {}", device.info().name), Severity::Error, Instant::now())),
+      Err(e) => self.status = Some((format!("Failed to record: {}", e), Severity::Error, Instant::now()))
+    }
+  }
+
+  /// Samples the recorded device's app state in the background, when the recorder wants a sample.
+  fn sample_state(&mut self) {
+    let Some(id) = self.recorder.as_mut().and_then(Recorder::sample_due) else { return };
+
+    let Some(device) = self.devices.items().iter().find(|d| d.id() == id).cloned() else {
+      // the device is gone for now, try again next time
+      return self.on_state_sampled(None)
+    };
+
+    let sender = self.sender.clone();
+    self.pending.push(tokio::spawn(async move {
+      let state = device.app_state().await;
+      let _ = sender.send(Event::StateSampled(state));
+    }));
+  }
+
+  /// Writes a sample to the recording. Failed samples are skipped quietly, the next one will do.
+  fn on_state_sampled(&mut self, state: Option<AppState>) {
+    let Some(recorder) = &mut self.recorder else { return };
+
+    if let Err(e) = recorder.sampled(state) {
+      self.status = Some((format!("Failed to record: {}", e), Severity::Error, Instant::now()));
+    }
+  }
+
+  /// Opens text entry, for devices that accept typed text.
+  fn start_typing(&mut self) {
+    let Some(device) = self.devices.selected() else { return };
+
+    if device.capabilities().has(Capability::Text) {
+      self.typing = Some(String::new());
+    } else {
+      self.status = Some((format!("{} does not accept typed text", device.info().name), Severity::Error, Instant::now()));
+    }
+  }
+
+  /// Sends input to the selected device, reporting failures in the status line as failing to send `what`.
+  fn send(&mut self, input: DeviceInput, what: String) {
+    let Some(device) = self.devices.selected().cloned() else { return };
+    self.record_input(&device, &input);
+
+    let delivered = self.queue(&device).send(device.clone(), input);
+    let sender = self.sender.clone();
+    self.pending.push(tokio::spawn(async move {
+      if let Err(e) = delivered.await {
+        if let mote::Error::Permission(_) = e { let _ = sender.send(Event::ControlRefused(device.id())); }
+        let _ = sender.send(Event::Error(format!("Failed to send {}: {}", what, explain(&e))));
+      }
+    }));
+  }
+
+  /// Loads the selected TV's channel list in the background; it opens as a menu once it arrives.
+  fn open_channels(&mut self) {
+    let Some(device) = self.devices.selected().cloned() else { return };
+
+    if !device.capabilities().has(Capability::Tuner) {
+      self.status = Some((format!("{} has no tuner", device.info().name), Severity::Error, Instant::now()));
+      return
+    }
+
+    let sender = self.sender.clone();
+    self.pending.push(tokio::spawn(async move {
+      let result = match device.channels().await {
+        // not being tuned to anything is no reason to withhold the list
+        Ok(channels) => Ok((channels, device.active_channel().await.unwrap_or(None))),
+        Err(e) => Err(e)
+      };
+
+      let _ = sender.send(Event::ChannelsLoaded(device.id(), result));
+    }));
+  }
+
+  /// Opens the channel menu on the active channel, unless the user has moved to another device meanwhile.
+  fn on_channels_loaded(&mut self, id: String, result: anyhow::Result<(Vec<Channel>, Option<Channel>)>) {
+    if self.devices.selected().map(|d| d.id()).as_deref() != Some(id.as_str()) { return }
+
+    match result {
+      Ok((channels, _)) if channels.is_empty() => {
+        self.status = Some(("No channels found, run a channel scan on the TV".to_owned(), Severity::Info, Instant::now()));
+      },
+      Ok((channels, active)) => {
+        let selected = active
+          .and_then(|active| channels.iter().position(|channel| channel.number == active.number))
+          .unwrap_or(0);
+
+        let entries = channels
+          .into_iter()
+          .map(|channel| MenuEntry { label: format!("{:>6}  {}", channel.number, channel.name), input: DeviceInput::Tune(channel.number), icon: None })
+          .collect();
+
+        self.menu = Some(Menu::new("Channels", entries, selected));
+      },
+      Err(e) => self.status = Some((format!("Failed to load channels: {}", e), Severity::Error, Instant::now()))
+    }
+  }
+
+  /// Loads the selected device's apps in the background; they open as a launcher menu once they arrive.
+  fn open_apps(&mut self) {
+    let Some(device) = self.devices.selected().cloned() else { return };
+
+    if !device.capabilities().has(Capability::Apps) {
+      self.status = Some((format!("{} cannot list its apps", device.info().name), Severity::Error, Instant::now()));
+      return
+    }
+
+    let sender = self.sender.clone();
+    self.pending.push(tokio::spawn(async move {
+      let apps = device.apps().await;
+      let _ = sender.send(Event::AppsLoaded(device.id(), apps));
+    }));
+  }
+
+  /// Opens the app launcher, unless the user has moved to another device meanwhile.
+  fn on_apps_loaded(&mut self, id: String, result: anyhow::Result<Vec<App>>) {
+    if self.devices.selected().map(|d| d.id()).as_deref() != Some(id.as_str()) { return }
+
+    match result {
+      Ok(apps) if apps.is_empty() => self.status = Some(("No apps installed".to_owned(), Severity::Info, Instant::now())),
+      Ok(apps) => {
+        let entries = apps
+          .into_iter()
+          .map(|app| MenuEntry { label: app.name, input: DeviceInput::Launch(app.id.clone()), icon: Some(app.id) })
+          .collect();
+
+        self.menu = Some(Menu::new("Apps", entries, 0));
+      },
+      Err(e) => self.status = Some((format!("Failed to load apps: {}", e), Severity::Error, Instant::now()))
+    }
+  }
+
+  /// Opens the input switcher, for TVs that have inputs to switch between.
+  fn open_inputs(&mut self) {
+    let Some(device) = self.devices.selected() else { return };
+
+    if !device.capabilities().has(Capability::TvInputs) {
+      self.status = Some((format!("{} has no inputs to switch", device.info().name), Severity::Error, Instant::now()));
+      return
+    }
+
+    let entries = TvInput::ALL
+      .iter()
+      .map(|input| MenuEntry { label: input.to_string(), input: DeviceInput::SwitchInput(*input), icon: None })
+      .collect();
+
+    self.menu = Some(Menu::new("Inputs", entries, 0));
+  }
+
+  /// Moves through the open menu: Enter or Space picks the highlighted entry, Esc closes it.
+  fn on_menu_key(&mut self, key: Key) {
+    let Some(menu) = &mut self.menu else { return };
+
     match key {
-      Key::Delete | Key::Backspace => self.send(RokuKey::Back.into()),
-      Key::Esc => self.send(RokuKey::Home.into()),
-
-      Key::Up => self.send(RokuKey::VolumeUp.into()),
-      Key::Down => self.send(RokuKey::VolumeDown.into()),
-      Key::Left => self.send(RokuKey::InstantReplay.into()),
-      Key::BackTab => {
-        if self.selected_device_index == 0 {
-          if self.devices.len() != 0 {
-            self.selected_device_index = self.devices.len()
+      Key::Up | Key::Char('w' | 'W') => menu.previous(),
+      Key::Down | Key::Char('s' | 'S') => menu.next(),
+      Key::Char('\n' | ' ') => {
+        let picked = menu.selected().cloned();
+        self.menu = None;
+
+        if let Some(MenuEntry { label, input, .. }) = picked { self.send(input, label.trim().to_owned()); }
+      },
+      Key::Esc => self.menu = None,
+      _ => ()
+    }
+  }
+
+  /// Edits the text entry line: Enter sends it, Esc abandons it.
+  fn on_typing_key(&mut self, key: Key) {
+    let Some(text) = &mut self.typing else { return };
+
+    match key {
+      Key::Char('\n') => {
+        let text = self.typing.take().unwrap_or_default();
+        if !text.is_empty() { self.send(DeviceInput::Text(text), "text".to_owned()); }
+      },
+      Key::Char(c) => text.push(c),
+      Key::Backspace | Key::Delete => { text.pop(); },
+      Key::Esc => self.typing = None,
+      _ => ()
+    }
+  }
+
+  /// Opens the developer panel on the selected device's screen, or closes it.
+  fn toggle_mirror(&mut self) {
+    if self.mirror.take().is_some() { return }
+    let Some(device) = self.devices.selected() else { return };
+
+    if !device.capabilities().has(Capability::Screenshot) {
+      self.status = Some((format!("{} cannot capture its screen, is developer mode enabled?", device.info().name), Severity::Error, Instant::now()));
+      return
+    }
+
+    self.mirror = Some(Mirror::new(device.id()));
+    self.capture_screen(Duration::ZERO);
+  }
+
+  /// Captures the mirrored screen in the background after `settle`, unless a capture is
+  /// already running, in which case another follows it.
+  fn capture_screen(&mut self, settle: Duration) {
+    let Some(mirror) = &mut self.mirror else { return };
+    if !mirror.request() { return }
+
+    let Some(device) = self.devices.items().iter().find(|d| d.id() == mirror.device).cloned() else { return };
+
+    let sender = self.sender.clone();
+    self.pending.push(tokio::spawn(async move {
+      tokio::time::sleep(settle).await;
+      let screen = device.screenshot().await.and_then(|bytes| Image::decode(&bytes));
+      let _ = sender.send(Event::ScreenCaptured(device.id(), screen));
+    }));
+  }
+
+  /// Shows a fresh capture, and starts the next one if more keys were pressed meanwhile.
+  fn on_screen_captured(&mut self, id: String, screen: anyhow::Result<Image>) {
+    let Some(mirror) = self.mirror.as_mut().filter(|mirror| mirror.device == id) else { return };
+
+    let screen = screen.map_err(|e| self.status = Some((format!("Failed to capture screen: {}", e), Severity::Error, Instant::now()))).ok();
+    if mirror.captured(screen) { self.capture_screen(SCREENSHOT_SETTLE); }
+  }
+
+  /// Opens the log pane on the selected device's debug console, or closes it.
+  fn toggle_log(&mut self) {
+    if self.log.is_some() { return self.close_log() }
+    let Some(device) = self.devices.selected().cloned() else { return };
+
+    if !device.capabilities().has(Capability::DebugConsole) {
+      self.status = Some((format!("{} has no debug console, is developer mode enabled?", device.info().name), Severity::Error, Instant::now()));
+      return
+    }
+
+    self.log = Some(LogView::new(device.id()));
+
+    let sender = self.sender.clone();
+    self.log_task = Some(tokio::spawn(async move {
+      let id = device.id();
+      let result = match device.debug_console().await {
+        Ok(mut lines) => {
+          while let Some(line) = lines.recv().await {
+            if sender.send(Event::LogLine(id.clone(), line)).is_err() { return }
           }
-        } else {
-          self.selected_device_index -= 1;
-        }
+
+          Ok(())
+        },
+        Err(e) => Err(e)
+      };
+
+      let _ = sender.send(Event::LogClosed(id, result));
+    }));
+  }
+
+  /// Closes the log pane, hanging up on the console.
+  fn close_log(&mut self) {
+    self.log = None;
+    if let Some(task) = self.log_task.take() { task.abort(); }
+  }
+
+  fn on_log_line(&mut self, id: String, line: LogLine) {
+    if let Some(log) = self.log.as_mut().filter(|log| log.device == id) { log.push(line); }
+  }
+
+  /// Marks the log pane disconnected, saying why if the console could not be reached.
+  fn on_log_closed(&mut self, id: String, result: anyhow::Result<()>) {
+    let Some(log) = self.log.as_mut().filter(|log| log.device == id) else { return };
+    log.connected = false;
+
+    self.status = Some(match result {
+      Ok(()) => ("Debug console closed".to_owned(), Severity::Info, Instant::now()),
+      Err(e) => (format!("{:#}", e), Severity::Error, Instant::now())
+    });
+  }
+
+  /// Opens filter entry for the log pane, starting from the current filter.
+  fn start_filter(&mut self) {
+    if let Some(log) = &mut self.log { log.editing = Some(log.filter().to_owned()); }
+  }
+
+  /// Edits the log pane's filter: Enter applies it (an empty one shows everything), Esc abandons it.
+  fn on_filter_key(&mut self, key: Key) {
+    let Some(log) = &mut self.log else { return };
+    let Some(filter) = &mut log.editing else { return };
+
+    match key {
+      Key::Char('\n') => log.apply_filter(),
+      Key::Char(c) => filter.push(c),
+      Key::Backspace | Key::Delete => { filter.pop(); },
+      Key::Esc => log.editing = None,
+      _ => ()
+    }
+  }
+
+  /// Saves everything the log pane has received to a file in the working directory.
+  fn save_log(&mut self) {
+    let Some(log) = &self.log else { return };
+
+    let seconds = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    let path = PathBuf::from(format!("mote-{}-{}.log", log.device, seconds));
+
+    self.status = Some(match log.save(&path) {
+      Ok(count) => (format!("Saved {} lines to {}", count, path.display()), Severity::Info, Instant::now()),
+      Err(e) => (format!("Failed to save log: {}", e), Severity::Error, Instant::now())
+    });
+  }
+
+  /// Records how a keypress request turned out, restarting its flash in the matching color.
+  fn on_key_sent(&mut self, id: String, key: RemoteKey, result: mote::Result<()>) {
+    let press = match result {
+      Ok(()) => {
+        self.refused.remove(&id);
+        self.capture_screen(SCREENSHOT_SETTLE);
+        Press::Delivered
       },
+      Err(e) => {
+        if let mote::Error::Permission(_) = e { self.refused.insert(id); }
+        self.status = Some((format!("Failed to send {:?}: {}", key, explain(&e)), Severity::Error, Instant::now()));
+        Press::Failed
+      }
+    };
+
+    self.active_keys.lock().unwrap().insert(key, (press, Instant::now()));
+  }
+
+  /// Presses whichever button was clicked, or selects whichever device tab was clicked.
+  fn on_click(&mut self, column: u16, row: u16) {
+    let Some(layout) = &self.layout else { return };
+
+    if let Some(key) = layout.button_at(column, row) {
+      self.press(key);
+    } else if let Some(index) = layout.tab_at(&self.tab_titles(), column, row) {
+      self.devices.select_index(index);
+    }
+  }
+
+  async fn on_key(&mut self, key: Key) -> bool {
+    if self.typing.is_some() {
+      match key {
+        Key::Ctrl('c' | 'd' | 'C' | 'D') => return true,
+        _ => self.on_typing_key(key)
+      }
+
+      return false
+    }
+
+    if self.log.as_ref().is_some_and(|log| log.editing.is_some()) {
+      match key {
+        Key::Ctrl('c' | 'd' | 'C' | 'D') => return true,
+        _ => self.on_filter_key(key)
+      }
+
+      return false
+    }
+
+    if self.menu.is_some() {
+      match key {
+        Key::Ctrl('c' | 'd' | 'C' | 'D') => return true,
+        _ => self.on_menu_key(key)
+      }
+
+      return false
+    }
+
+    match key {
+      Key::Delete | Key::Backspace => self.press(RemoteKey::Back),
+      Key::Esc => self.press(RemoteKey::Home),
+
+      Key::Up => self.press(RemoteKey::VolumeUp),
+      Key::Down => self.press(RemoteKey::VolumeDown),
+      Key::Left => self.press(RemoteKey::InstantReplay),
+      Key::BackTab => self.devices.previous(),
+      Key::PageUp => if let Some(log) = &mut self.log { log.scroll_up(LOG_SCROLL_LINES) },
+      Key::PageDown => if let Some(log) = &mut self.log { log.scroll_down(LOG_SCROLL_LINES) },
 
       Key::Char(k) => match k.to_ascii_lowercase() {
         // CLU UI controls
-        '\t' => self.selected_device_index = (self.selected_device_index + 1) % self.devices.len(),
+        '\t' => self.devices.next(),
+        '1'..='9' => { self.devices.select_index(k as usize - '1' as usize); },
         'i' | 'I'  => self.context = match self.context {
           UIContext::Main => UIContext::DeviceInfo,
           UIContext::DeviceInfo => UIContext::Main
         },
 
+        '/'        => self.start_typing(),
+        'c' | 'C'  => self.open_channels(),
+        'n' | 'N'  => self.open_inputs(),
+        'l' | 'L'  => self.open_apps(),
+        'v' | 'V'  => self.toggle_mirror(),
+        'o' | 'O'  => self.toggle_log(),
+
         // special control keys
-        'p' | 'P'  => self.send(RokuKey::Power.into()),
-        'h' | 'H'  => self.send(RokuKey::Home.into()),
-        'm' | 'M'  => self.send(RokuKey::VolumeMute.into()),
-        '*'        => self.send(RokuKey::Info.into()),
+        'p' | 'P'  => self.press(RemoteKey::Power),
+        'h' | 'H'  => self.press(RemoteKey::Home),
+        'm' | 'M'  => self.press(RemoteKey::VolumeMute),
+        '*'        => self.press(RemoteKey::Info),
+        'f' | 'F'  => self.press(RemoteKey::FindRemote),
 
         // arrow pad keys
-        'w' | 'W' => self.send(RokuKey::PadUp.into()),
-        'a' | 'A' => self.send(RokuKey::PadLeft.into()),
-        's' | 'S' => self.send(RokuKey::PadDown.into()),
-        'd' | 'D' => self.send(RokuKey::PadRight.into()),
-        ' '       => self.send(RokuKey::Ok.into()),
+        'w' | 'W' => self.press(RemoteKey::Up),
+        'a' | 'A' => self.press(RemoteKey::Left),
+        's' | 'S' => self.press(RemoteKey::Down),
+        'd' | 'D' => self.press(RemoteKey::Right),
+        ' '       => self.press(RemoteKey::Ok),
 
         _ => return false
       },
@@ -279,6 +1007,8 @@ impl UI {
       Key::Ctrl(k) => {
         match k.to_ascii_lowercase() {
           'c' | 'd' | 'C' | 'D' => return true,
+          'f' => self.start_filter(),
+          's' => self.save_log(),
           _ => ()
         }
       },
@@ -288,36 +1018,81 @@ impl UI {
     false
   }
 
-  /// Handles input and discovery events, refreshing the UI after eache event.
-  /// Only redraws when an event has occurred, however does not perform any logic
-  /// to determine if the UI actually needs to be re-rendered, so is maybe 
-  /// slightly suboptimal depending on the cost of this logic.
-  pub async fn listen(&mut self, rx: UnboundedReceiver<Device>) {
-    let mut input = user_input();
-    let mut press = Box::pin(input.next());
+  /// Expires time-based state (pad flashes, stale status messages) and samples the recorded device when due.
+  fn on_tick(&mut self) {
+    self.pending.retain(|p| !p.is_finished());
+    self.sample_state();
+
+    self.active_keys
+      .lock()
+      .unwrap()
+      .retain(|_, (press, since)| press.is_live(*since));
+
+    if let Some((_, _, at)) = &self.status {
+      if at.elapsed() >= STATUS_DURATION { self.status = None; }
+    }
+  }
 
-    let mut discovery = UnboundedReceiverStream::new(rx);
-    let mut device = Box::pin(discovery.next());
+  /// Drives the UI from a single event stream: keypresses, discovery, device polling,
+  /// errors and a periodic tick. Redraws after every event, so time-based state (pad
+  /// flashes, live uptime, poll results) stays current without user input.
+  pub async fn listen(&mut self, discovery: UnboundedReceiver<Device>) {
+    let tx = self.sender.clone();
 
-    loop {      
-      match select(press, device).await {
-        Either::Left((k, f)) => {
-          if let Some(key) = k { if self.on_key(key).await { break; } } 
-          else { break }
+    user_input(tx.clone());
+    self.tasks.push(event::forward_discovery(discovery, tx.clone()));
+    self.tasks.push(event::tick(tx.clone()));
+    self.tasks.push(event::signals(tx.clone()));
 
-          press = Box::pin(input.next());
-          device = f;
+    while let Some(event) = self.events.recv().await {
+      match event {
+        Event::Input(UserInput::Key(key)) => if self.on_key(key).await { break },
+        Event::Input(UserInput::Click { column, row }) => self.on_click(column, row),
+        Event::DeviceAdded(device) => {
+          self.tasks.push(event::poll(device.clone(), tx.clone()));
+          self.devices.upsert(device);
+        },
+        Event::DevicePolled(device) => self.devices.upsert(device),
+        Event::DeviceRemoved(id) => {
+          self.queues.remove(&id);
+          self.devices.remove_where(|d| d.id() == id)
         },
-        Either::Right((d, f)) => {
-          if let Some(device) = d { self.devices.push(device); } 
-          else { break }
+        Event::KeySent(id, key, result) => self.on_key_sent(id, key, result),
+        Event::ControlRefused(id) => { self.refused.insert(id); },
+        Event::ChannelsLoaded(id, result) => self.on_channels_loaded(id, result),
+        Event::AppsLoaded(id, result) => self.on_apps_loaded(id, result),
+        Event::IconLoaded(id, app_id, icon) => { self.icons.insert((id, app_id), icon.ok()); },
+        Event::ScreenCaptured(id, screen) => self.on_screen_captured(id, screen),
+        Event::LogLine(id, line) => self.on_log_line(id, line),
+        Event::LogClosed(id, result) => self.on_log_closed(id, result),
+        Event::StateSampled(state) => self.on_state_sampled(state.ok()),
+        Event::Tick => self.on_tick(),
+        Event::Error(message) => self.status = Some((message, Severity::Error, Instant::now())),
+        Event::Shutdown => break
+      }
 
-          press = f;
-          device = Box::pin(discovery.next())
-        }
+      // the developer panel mirrors one device, and closes once another is selected
+      if self.mirror.as_ref().is_some_and(|mirror| self.devices.selected().map(|d| d.id()).as_deref() != Some(mirror.device.as_str())) {
+        self.mirror = None;
+      }
+
+      // as does the log pane
+      if self.log.as_ref().is_some_and(|log| self.devices.selected().map(|d| d.id()).as_deref() != Some(log.device.as_str())) {
+        self.close_log();
       }
 
       self.render();
     }
+
+    self.shutdown().await;
+  }
+
+  /// Stops background work: discovery and polling are cancelled outright, keypresses
+  /// already sent get a short grace period so the last press is not lost.
+  async fn shutdown(&mut self) {
+    self.prepare_pictures(vec![]);
+    self.close_log();
+    for task in self.tasks.drain(..) { task.abort(); }
+    let _ = timeout(SHUTDOWN_GRACE, join_all(self.pending.drain(..))).await;
   }
 }
\ No newline at end of file
@@ -0,0 +1,236 @@
+use mote::devices::{Device, RemoteDevice};
+
+/// Something with a stable identity that survives being re-discovered or re-polled.
+pub trait Identified {
+  fn id(&self) -> String;
+}
+
+impl Identified for Device {
+  fn id(&self) -> String { RemoteDevice::id(self.as_ref()) }
+}
+
+/// An ordered list with one selected entry. The selection follows the entry's identity,
+/// not its position, so devices appearing or disappearing never silently move it to a
+/// different device.
+#[derive(Debug)]
+pub struct Selection<T> {
+  items: Vec<T>,
+  selected: Option<String>
+}
+
+impl<T: Identified> Selection<T> {
+  pub fn new() -> Self {
+    Selection { items: vec![], selected: None }
+  }
+
+  pub fn items(&self) -> &[T] { &self.items }
+
+  /// Position of the selected entry, `None` only when the list is empty.
+  pub fn selected_index(&self) -> Option<usize> {
+    let id = self.selected.as_deref()?;
+    self.items.iter().position(|i| i.id() == id)
+  }
+
+  pub fn selected(&self) -> Option<&T> {
+    self.selected_index().map(|i| &self.items[i])
+  }
+
+  /// Adds an entry, or replaces the one with the same identity in place.
+  /// The first entry to arrive becomes selected.
+  pub fn upsert(&mut self, item: T) {
+    match self.items.iter_mut().find(|i| i.id() == item.id()) {
+      Some(known) => *known = item,
+      None => {
+        if self.selected.is_none() { self.selected = Some(item.id()); }
+        self.items.push(item);
+      }
+    }
+  }
+
+  /// Removes every entry matching `predicate`. If the selected entry goes, the entry
+  /// that slides into its position is selected instead (or the new last entry).
+  pub fn remove_where(&mut self, predicate: impl Fn(&T) -> bool) {
+    let previous_index = self.selected_index();
+    self.items.retain(|i| !predicate(i));
+
+    if self.selected_index().is_none() {
+      self.selected = previous_index
+        .and_then(|i| self.items.get(i.min(self.items.len().saturating_sub(1))))
+        .map(|i| i.id());
+    }
+  }
+
+  /// Selects the entry at `index`, ignoring out of range indices. Returns whether it did anything.
+  pub fn select_index(&mut self, index: usize) -> bool {
+    match self.items.get(index) {
+      Some(item) => { self.selected = Some(item.id()); true },
+      None => false
+    }
+  }
+
+  /// Moves the selection forward one entry, wrapping around.
+  pub fn next(&mut self) {
+    if let Some(i) = self.selected_index() {
+      self.select_index((i + 1) % self.items.len());
+    }
+  }
+
+  /// Moves the selection back one entry, wrapping around.
+  pub fn previous(&mut self) {
+    if let Some(i) = self.selected_index() {
+      self.select_index((i + self.items.len() - 1) % self.items.len());
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{net::SocketAddr, sync::Arc};
+  use futures::future::BoxFuture;
+  use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
+  use mote::devices::{Capabilities, DeviceInfo, DeviceInput, DeviceType, RemoteDevice};
+  use super::*;
+
+  #[derive(Debug, PartialEq)]
+  struct Item(&'static str, u32);
+
+  impl Identified for Item {
+    fn id(&self) -> String { self.0.into() }
+  }
+
+  fn selection(ids: &[&'static str]) -> Selection<Item> {
+    let mut s = Selection::new();
+    for id in ids { s.upsert(Item(id, 0)); }
+    s
+  }
+
+  fn selected_id(s: &Selection<Item>) -> Option<&'static str> {
+    s.selected().map(|i| i.0)
+  }
+
+  #[test]
+  fn empty_selection_has_nothing_selected() {
+    let mut s = selection(&[]);
+    assert!(s.items().is_empty());
+    assert_eq!(s.selected_index(), None);
+
+    // none of these may panic on an empty list
+    s.next();
+    s.previous();
+    assert!(!s.select_index(0));
+    s.remove_where(|_| true);
+    assert_eq!(s.selected_index(), None);
+  }
+
+  #[test]
+  fn first_item_is_selected() {
+    let s = selection(&["a", "b"]);
+    assert_eq!(selected_id(&s), Some("a"));
+  }
+
+  #[test]
+  fn next_and_previous_wrap_around() {
+    let mut s = selection(&["a", "b", "c"]);
+
+    s.previous();
+    assert_eq!(selected_id(&s), Some("c"));
+
+    s.next();
+    assert_eq!(selected_id(&s), Some("a"));
+
+    s.next();
+    s.next();
+    assert_eq!(selected_id(&s), Some("c"));
+  }
+
+  #[test]
+  fn selection_follows_identity_across_insertions() {
+    let mut s = selection(&["a", "b"]);
+    s.next();
+
+    s.upsert(Item("c", 0));
+    assert_eq!(selected_id(&s), Some("b"));
+  }
+
+  #[test]
+  fn upsert_replaces_in_place() {
+    let mut s = selection(&["a", "b"]);
+    s.next();
+
+    s.upsert(Item("b", 7));
+    assert_eq!(s.items().len(), 2);
+    assert_eq!(s.selected(), Some(&Item("b", 7)));
+  }
+
+  #[test]
+  fn selection_follows_identity_across_removals() {
+    let mut s = selection(&["a", "b", "c"]);
+    s.select_index(2);
+
+    s.remove_where(|i| i.0 == "a");
+    assert_eq!(selected_id(&s), Some("c"));
+    assert_eq!(s.selected_index(), Some(1));
+  }
+
+  #[test]
+  fn removing_selected_selects_neighbour() {
+    let mut s = selection(&["a", "b", "c"]);
+    s.select_index(1);
+
+    s.remove_where(|i| i.0 == "b");
+    assert_eq!(selected_id(&s), Some("c"));
+
+    s.remove_where(|i| i.0 == "c");
+    assert_eq!(selected_id(&s), Some("a"));
+
+    s.remove_where(|i| i.0 == "a");
+    assert_eq!(selected_id(&s), None);
+
+    // the next arrival is selected again
+    s.upsert(Item("d", 0));
+    assert_eq!(selected_id(&s), Some("d"));
+  }
+
+  /// A Roku that reports only what `info` says
+  #[derive(Debug)]
+  struct Reported { info: DeviceInfo, location: SocketAddr }
+
+  impl RemoteDevice for Reported {
+    fn discover() -> UnboundedReceiver<Device> { unbounded_channel().1 }
+    fn device_type(&self) -> DeviceType { DeviceType::Roku }
+    fn location(&self) -> Option<SocketAddr> { Some(self.location) }
+    fn info(&self) -> &DeviceInfo { &self.info }
+    fn capabilities(&self) -> Capabilities { Capabilities::default() }
+    fn send_input(&self, _: DeviceInput) -> BoxFuture<'_, mote::Result<()>> { Box::pin(async { Ok(()) }) }
+    fn query_state(&self) -> BoxFuture<'_, mote::Result<Device>> { Box::pin(async { Err(anyhow::anyhow!("not queried").into()) }) }
+  }
+
+  fn device(serial_number: &str, location: &str) -> Device {
+    let mut info = DeviceInfo::default();
+    info.product.serial_number = serial_number.into();
+    Arc::new(Reported { info, location: location.parse().unwrap() })
+  }
+
+  #[test]
+  fn devices_without_serial_numbers_stay_apart() {
+    let mut s = Selection::new();
+    s.upsert(device("", "192.168.1.20:8060"));
+    s.upsert(device("", "192.168.1.21:8060"));
+    s.upsert(device("", "192.168.1.20:8060"));
+
+    assert_eq!(s.items().len(), 2);
+    assert_eq!(s.items()[0].id(), "Roku@192.168.1.20");
+    assert_eq!(device("X00400ABCDEF", "192.168.1.20:8060").id(), "X00400ABCDEF");
+  }
+
+  #[test]
+  fn select_index_ignores_out_of_range() {
+    let mut s = selection(&["a", "b"]);
+
+    assert!(!s.select_index(5));
+    assert_eq!(selected_id(&s), Some("a"));
+
+    assert!(s.select_index(1));
+    assert_eq!(selected_id(&s), Some("b"));
+  }
+}
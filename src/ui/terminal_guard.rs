@@ -0,0 +1,50 @@
+use std::{io::Write, os::unix::io::AsRawFd, sync::{Once, OnceLock}};
+
+/// Terminal settings from before we switched to raw mode
+static ORIGINAL_TERMIOS: OnceLock<libc::termios> = OnceLock::new();
+static INSTALL_PANIC_HOOK: Once = Once::new();
+
+const EXIT_MOUSE_SEQUENCE: &str = "\x1b[?1006l\x1b[?1015l\x1b[?1002l\x1b[?1000l";
+
+/// Puts the terminal back the way we found it (cooked mode, visible cursor, no mouse
+/// reporting) when dropped, and from a panic hook so a panic message is readable and
+/// does not leave the shell in raw mode. Only panics on the main thread, where the UI
+/// runs, restore it; the UI carries on drawing after a background thread panics.
+///
+/// Must be created before the terminal is put into raw mode.
+pub struct TerminalGuard;
+
+impl TerminalGuard {
+  pub fn new() -> Self {
+    ORIGINAL_TERMIOS.get_or_init(|| {
+      let mut termios = unsafe { std::mem::zeroed::<libc::termios>() };
+      unsafe { libc::tcgetattr(std::io::stdout().as_raw_fd(), &mut termios) };
+      termios
+    });
+
+    INSTALL_PANIC_HOOK.call_once(|| {
+      let default_hook = std::panic::take_hook();
+      std::panic::set_hook(Box::new(move |info| {
+        if std::thread::current().name() == Some("main") { restore(); }
+        default_hook(info);
+      }));
+    });
+
+    TerminalGuard
+  }
+}
+
+impl Drop for TerminalGuard {
+  fn drop(&mut self) { restore() }
+}
+
+/// Restores the terminal. Safe to call any number of times.
+fn restore() {
+  let mut stdout = std::io::stdout();
+  let _ = write!(stdout, "{}{}", EXIT_MOUSE_SEQUENCE, termion::cursor::Show);
+  let _ = stdout.flush();
+
+  if let Some(termios) = ORIGINAL_TERMIOS.get() {
+    unsafe { libc::tcsetattr(stdout.as_raw_fd(), libc::TCSANOW, termios) };
+  }
+}
@@ -1,7 +1,72 @@
-use futures::{future::ready, Stream, StreamExt};
-use termion::{event::Key, input::TermRead};
-use tokio_stream::iter;
+use termion::{event::{Event as TermionEvent, Key as TermionKey, MouseButton, MouseEvent}, input::TermRead};
+use tokio::sync::mpsc::UnboundedSender;
+use super::event::Event;
 
-pub fn user_input() -> impl Stream<Item = Key> + Unpin {
-  iter(std::io::stdin().keys()).filter_map(|r| ready(r.ok()))
-}
\ No newline at end of file
+/// Backend-independent keys. The UI only ever sees these, so terminal backends can be
+/// swapped without touching keybindings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Key {
+  Char(char),
+  Ctrl(char),
+  Up,
+  Down,
+  Left,
+  Right,
+  PageUp,
+  PageDown,
+  BackTab,
+  Backspace,
+  Delete,
+  Esc
+}
+
+/// Anything the user can do to the UI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserInput {
+  Key(Key),
+
+  /// A left click at a zero-based cell
+  Click { column: u16, row: u16 }
+}
+
+impl UserInput {
+  /// Translates a termion event, dropping anything the UI does not act on.
+  fn from_termion(event: TermionEvent) -> Option<Self> {
+    let key = match event {
+      TermionEvent::Key(k) => match k {
+        TermionKey::Char(c)   => Key::Char(c),
+        TermionKey::Ctrl(c)   => Key::Ctrl(c),
+        TermionKey::Up        => Key::Up,
+        TermionKey::Down      => Key::Down,
+        TermionKey::Left      => Key::Left,
+        TermionKey::Right     => Key::Right,
+        TermionKey::PageUp    => Key::PageUp,
+        TermionKey::PageDown  => Key::PageDown,
+        TermionKey::BackTab   => Key::BackTab,
+        TermionKey::Backspace => Key::Backspace,
+        TermionKey::Delete    => Key::Delete,
+        TermionKey::Esc       => Key::Esc,
+        _ => return None
+      },
+
+      // termion reports one-based coordinates
+      TermionEvent::Mouse(MouseEvent::Press(MouseButton::Left, x, y)) => return Some(UserInput::Click {
+        column: x.saturating_sub(1),
+        row: y.saturating_sub(1)
+      }),
+
+      _ => return None
+    };
+
+    Some(UserInput::Key(key))
+  }
+}
+
+/// Reads stdin on a dedicated thread (the read blocks) and forwards keys and clicks as events.
+pub fn user_input(tx: UnboundedSender<Event>) {
+  std::thread::spawn(move || {
+    for input in std::io::stdin().events().filter_map(Result::ok).filter_map(UserInput::from_termion) {
+      if tx.send(Event::Input(input)).is_err() { break }
+    }
+  });
+}